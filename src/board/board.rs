@@ -1,26 +1,47 @@
-//todo: forced 3 fold repetition, simplified table, hashing
-use bevy::ecs::system::Resource;
-
+//todo: simplified table
 use super::{
+  attacks::{bishop_attacks, king_attacks, knight_attacks, pawn_attacks, rook_attacks},
+  bitboard::Bitboard,
   board_movement_trait::BoardMovement,
   cached_piece_moves::CachedPieceMoves,
+  fen_error::FenError,
   move_gen::MoveGen,
-  move_input::{MoveInput, BISHOP, KNIGHT, QUEEN, ROOK},
+  move_input::MoveInput,
+  move_list::MoveList,
+  piece::{Color, PieceKind},
   pieces::Pieces,
-  status::*,
+  square::Square,
+  status::GameStatus,
+  undo::Undo,
   util_fns::*,
+  zobrist,
 };
 
-#[derive(Resource)]
+const DARK_SQUARES: Bitboard = Bitboard(0xAA_55_AA_55_AA_55_AA_55);
+const PROMOTING_SQUARES: Bitboard = Bitboard(0xFF_00_00_00_00_00_00_FF);
+
+//bishops on a single square colour never attack the other one
+fn same_colored(bishops: Bitboard) -> bool {
+  !bishops.intersects(DARK_SQUARES) || !bishops.intersects(!DARK_SQUARES)
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "bevy", derive(bevy::ecs::system::Resource))]
 pub struct Board {
   pub white_turn: bool,
-  pub white: Pieces,
-  pub black: Pieces,
+  //private so nothing outside the board can desync the mailbox, read through white() and black()
+  pub(super) white: Pieces,
+  pub(super) black: Pieces,
   clock: u64,
   half_clock: u64,
   pub cached_moves: CachedPieceMoves,
+  hash: u64,
+  history: Vec<u64>,
+  //redundant square to piece lookup, kept in sync with the bitboards
+  mailbox: [Option<(Color, PieceKind)>; 64],
 
-  pub(super) en_passant_mask: u64,
+  //the square a pawn skipped with its double push, empty otherwise
+  pub(super) en_passant: Bitboard,
   pub(super) white_short_castle: bool,
   pub(super) white_long_castle: bool,
   pub(super) black_short_castle: bool,
@@ -28,9 +49,9 @@ pub struct Board {
 }
 
 //constructor
-impl Board {
-  pub fn default() -> Board {
-    Board {
+impl Default for Board {
+  fn default() -> Board {
+    let mut board = Board {
       white: Pieces::white(),
       black: Pieces::black(),
 
@@ -39,151 +60,197 @@ impl Board {
       black_short_castle: true,
       black_long_castle: true,
       ..Board::empty()
-    }
+    };
+    board.sync_mailbox(Bitboard::FULL);
+    board.hash = zobrist::hash(&board);
+    board
   }
+}
 
+impl Board {
   pub fn empty() -> Board {
-    Board {
+    let mut board = Board {
       white_turn: true,
       white: Pieces::empty(),
       black: Pieces::empty(),
       clock: 1,
       half_clock: 0,
       cached_moves: CachedPieceMoves::default(),
+      hash: 0,
+      history: Vec::new(),
+      mailbox: [None; 64],
 
-      en_passant_mask: 0,
+      en_passant: Bitboard::EMPTY,
       white_short_castle: false,
       white_long_castle: false,
       black_short_castle: false,
       black_long_castle: false,
-    }
+    };
+    board.hash = zobrist::hash(&board);
+    board
   }
 
   pub fn from_fen(fen_string: &str) -> Board {
+    Board::try_from_fen(fen_string).unwrap_or_else(|error| panic!("{error}"))
+  }
+
+  pub fn try_from_fen_strict(fen_string: &str) -> Result<Board, FenError> {
+    let board = Board::try_from_fen(fen_string)?;
+    let errors = board.validate();
+    if !errors.is_empty() {
+      return Err(FenError::Illegal(errors));
+    }
+    Ok(board)
+  }
+
+  pub fn try_from_fen(fen_string: &str) -> Result<Board, FenError> {
+    //parsing slices by byte offset, callers get char indices
+    Board::parse_fen(fen_string).map_err(|error| {
+      error.map_position(|byte| {
+        fen_string.get(..byte).map_or(byte, |before| before.chars().count())
+      })
+    })
+  }
+
+  fn parse_fen(fen_string: &str) -> Result<Board, FenError> {
     let mut board = Board::empty();
 
-    let slices: Vec<&str> = fen_string.split_whitespace().collect();
-    assert_eq!(slices.len(), 6);
+    let slices = fen_fields(fen_string);
+    if slices.len() != 6 {
+      return Err(FenError::FieldCount(fen_string.len()));
+    }
 
     //pieces
-    let pieces = slices[0];
-    let rows: Vec<&str> = pieces.split('/').collect();
-    assert_eq!(rows.len(), 8);
-
-    for (row_id, row) in rows.iter().enumerate() {
-      let mut total = 0;
-      for c in row.chars() {
-        let pos = (0x80_u64)
-          .move_right_mask(total)
-          .move_down_mask(row_id as u32);
-        match c {
-          'P' => board.white.pawns |= pos,
-          'N' => board.white.knights |= pos,
-          'B' => board.white.bishops |= pos,
-          'R' => board.white.rooks |= pos,
-          'Q' => board.white.queens |= pos,
-          'K' => board.white.king = pos,
-          'p' => board.black.pawns |= pos,
-          'n' => board.black.knights |= pos,
-          'b' => board.black.bishops |= pos,
-          'r' => board.black.rooks |= pos,
-          'q' => board.black.queens |= pos,
-          'k' => board.black.king = pos,
-          digit if c.is_ascii_digit() => {
-            assert_ne!(digit, '0');
-            assert_ne!(digit, '9');
-
-            total += digit.to_digit(10).unwrap() - 1;
-          }
-          wrong_char => {
-            panic!("Unexpected character ({wrong_char}) in piece placement data");
-          }
+    let (start, pieces) = slices[0];
+    let mut row_id = 0;
+    let mut total = 0;
+    for (i, c) in pieces.char_indices() {
+      let position = start + i;
+      if c == '/' {
+        if total != 8 || row_id == 7 {
+          return Err(FenError::Placement(position));
         }
-        total += 1;
+        row_id += 1;
+        total = 0;
+        continue;
       }
-      assert_eq!(
-        total,
-        8,
-        "The row {} contains {} squares",
-        row_id + 1,
-        total
-      );
-    }
 
-    //turn
-    match slices[1] {
-      "w" => board.white_turn = true,
-      "b" => board.white_turn = false,
-      wrong_char => {
-        panic!("Unexpected character ({wrong_char}) in active color data")
+      let pos = Bitboard(0x80).move_right_mask(total).move_down_mask(row_id);
+      match (c, PieceKind::from_letter(c)) {
+        ('1'..='8', _) => total += c.to_digit(10).unwrap() - 1,
+        (_, Some(kind)) if c.is_ascii_uppercase() => board.white.add_piece(kind, pos),
+        (_, Some(kind)) => board.black.add_piece(kind, pos),
+        _ => return Err(FenError::Placement(position)),
       }
+      total += 1;
+
+      if total > 8 {
+        return Err(FenError::Placement(position));
+      }
+    }
+    if total != 8 || row_id != 7 {
+      return Err(FenError::Placement(start + pieces.len()));
     }
 
+    //turn
+    let (start, turn) = slices[1];
+    board.white_turn = match turn {
+      "w" => true,
+      "b" => false,
+      _ => return Err(FenError::SideToMove(start)),
+    };
+
     //castling
-    if slices[2] != "-" {
-      let chars = slices[2].chars().collect::<Vec<char>>();
-
-      for c in chars {
-        match c {
-          'K' => board.white_short_castle = true,
-          'Q' => board.white_long_castle = true,
-          'k' => board.black_short_castle = true,
-          'q' => board.black_long_castle = true,
-          wrong_char => {
-            panic!("Unexpected character ({wrong_char}) in castling rights data")
-          }
+    let (start, castling) = slices[2];
+    if castling != "-" {
+      for (i, c) in castling.char_indices() {
+        let right = match c {
+          'K' => &mut board.white_short_castle,
+          'Q' => &mut board.white_long_castle,
+          'k' => &mut board.black_short_castle,
+          'q' => &mut board.black_long_castle,
+          _ => return Err(FenError::Castling(start + i)),
+        };
+        if *right {
+          return Err(FenError::Castling(start + i));
         }
+        *right = true;
       }
     }
 
     //en passant
-    if slices[3] != "-" {
-      let enp_chars = slices[3].chars().collect::<Vec<char>>();
-      assert_eq!(enp_chars.len(), 2);
-      let x = match enp_chars[0] {
-        'a' => 0,
-        'b' => 1,
-        'c' => 2,
-        'd' => 3,
-        'e' => 4,
-        'f' => 5,
-        'g' => 6,
-        'h' => 7,
-        wrong_char => {
-          panic!("Unexpected rank character ({wrong_char}) in en passant data")
-        }
-      };
+    let (start, en_passant) = slices[3];
+    if en_passant != "-" {
+      let enp_chars = en_passant.chars().collect::<Vec<char>>();
+      if enp_chars.len() != 2 {
+        return Err(FenError::EnPassant(start));
+      }
 
-      let y = match enp_chars[1] {
-        '1' => 0,
-        '2' => 1,
-        '3' => 2,
-        '4' => 3,
-        '5' => 4,
-        '6' => 5,
-        '7' => 6,
-        '8' => 7,
-        wrong_char => {
-          panic!("Unexpected file character ({wrong_char}) in en passant data")
-        }
-      };
+      if !('a'..='h').contains(&enp_chars[0]) {
+        return Err(FenError::EnPassant(start));
+      }
+      let square = en_passant
+        .parse::<Square>()
+        .map_err(|_| FenError::EnPassant(start + 1))?;
+      board.en_passant = square.into();
+    }
 
-      let shift = x + y * 8;
-      board.en_passant_mask = 1 << shift;
+    //clocks
+    let (start, half_clock) = slices[4];
+    if let Some(i) = half_clock.find(|c: char| !c.is_ascii_digit()) {
+      return Err(FenError::Halfmove(start + i));
     }
+    board.half_clock = half_clock
+      .parse::<u64>()
+      .map_err(|_| FenError::Halfmove(start))?;
 
-    board
+    let (start, clock) = slices[5];
+    if let Some(i) = clock.find(|c: char| !c.is_ascii_digit()) {
+      return Err(FenError::Fullmove(start + i));
+    }
+    board.clock = clock.parse::<u64>().map_err(|_| FenError::Fullmove(start))?;
+    if board.clock == 0 {
+      return Err(FenError::Fullmove(start));
+    }
+
+    board.sync_mailbox(Bitboard::FULL);
+    board.hash = zobrist::hash(&board);
+    Ok(board)
+  }
+}
+
+//whitespace separated fields together with the byte offset they start at
+fn fen_fields(fen_string: &str) -> Vec<(usize, &str)> {
+  let mut fields = Vec::new();
+  let mut start = None;
+
+  for (i, c) in fen_string.char_indices() {
+    match (c.is_whitespace(), start) {
+      (true, Some(s)) => {
+        fields.push((s, &fen_string[s..i]));
+        start = None;
+      }
+      (false, None) => start = Some(i),
+      _ => {}
+    }
+  }
+  if let Some(s) = start {
+    fields.push((s, &fen_string[s..]));
   }
+
+  fields
 }
 
 //moving/updating
 impl Board {
   pub fn move_piece(&mut self, input: MoveInput) -> bool {
-    let from_mask: u64 = input.from;
-    let to_mask: u64 = input.to;
+    let from_mask = Bitboard::from(input.from);
+    let white_before = self.white.clone();
+    let black_before = self.black.clone();
+    let state_before = zobrist::state_key(self);
+    let hash_before = self.hash;
 
-    self.update_cache(from_mask);
-    let move_mask = to_mask & self.cached_moves.all();
+    let move_mask = self.legal_move_mask(input);
 
     //order matters
     self.handle_en_passant(move_mask);
@@ -192,76 +259,206 @@ impl Board {
     self.handle_move(from_mask, move_mask, input.promotion);
 
     self.update_clocks(move_mask);
-    self.white_turn ^= move_mask > 0;
-    move_mask > 0
+    self.white_turn ^= !move_mask.is_empty();
+    self.sync_mailbox(white_before.changed(&self.white) | black_before.changed(&self.black));
+
+    self.hash ^= zobrist::pieces_delta(&white_before, &self.white, 0)
+      ^ zobrist::pieces_delta(&black_before, &self.black, 1)
+      ^ state_before
+      ^ zobrist::state_key(self);
+
+    //the cache describes the position before the move
+    if !move_mask.is_empty() {
+      self.cached_moves = CachedPieceMoves::default();
+      self.history.push(hash_before);
+    }
+    !move_mask.is_empty()
+  }
+
+  pub fn make_move(&mut self, input: MoveInput) -> Option<Undo> {
+    if self.legal_move_mask(input).is_empty() {
+      return None;
+    }
+    let (from, to) = (Bitboard::from(input.from), Bitboard::from(input.to));
+    let moved = match self.piece_at(input.from)? {
+      (color, kind) if color == self.side_to_move() => kind,
+      _ => return None,
+    };
+
+    let en_passant_capture = moved == PieceKind::Pawn && to.intersects(self.en_passant);
+    let captured_mask = if !en_passant_capture {
+      to
+    } else if self.white_turn {
+      to.move_down_mask(1)
+    } else {
+      to.move_up_mask(1)
+    };
+
+    let castle = moved == PieceKind::King;
+    let short_castle = castle && to == from.move_right_mask(2);
+    let long_castle = castle && to == from.move_left_mask(2);
+    let rook_from =
+      to.move_right_mask(1).only_if(short_castle) | to.move_left_mask(2).only_if(long_castle);
+    let rook_to =
+      to.move_left_mask(1).only_if(short_castle) | to.move_right_mask(1).only_if(long_castle);
+
+    let undo = Undo {
+      from: input.from,
+      to: input.to,
+      moved,
+      captured: captured_mask.first().and_then(|at| self.piece_at(at)).map(|(_, kind)| kind),
+      captured_mask,
+      rook_from,
+      rook_to,
+
+      hash: self.hash,
+      en_passant: self.en_passant,
+      half_clock: self.half_clock,
+      white_short_castle: self.white_short_castle,
+      white_long_castle: self.white_long_castle,
+      black_short_castle: self.black_short_castle,
+      black_long_castle: self.black_long_castle,
+    };
+
+    self.move_piece(input).then_some(undo)
   }
 
-  fn update_clocks(&mut self, move_mask: u64) {
-    let pawn_moved = move_mask & self.cached_moves.pawn_default > 0 || self.en_passant_mask > 0;
-    let capture_or_pawn = mask_from_bool(pawn_moved || move_mask & self.cached_moves.capturing > 0);
-    self.half_clock = if_bool(
-      move_mask > 0,
-      !capture_or_pawn & (self.half_clock + 1),
-      self.half_clock,
+  pub fn unmake_move(&mut self, undo: Undo) {
+    self.white_turn = !self.white_turn;
+    self.clock -= !self.white_turn as u64;
+
+    let (ally, enemy) = if self.white_turn {
+      (&mut self.white, &mut self.black)
+    } else {
+      (&mut self.black, &mut self.white)
+    };
+
+    ally.remove_piece(undo.to.into());
+    ally.add_piece(undo.moved, undo.from.into());
+    ally.move_piece(undo.rook_to, undo.rook_from);
+    if let Some(captured) = undo.captured {
+      enemy.add_piece(captured, undo.captured_mask);
+    }
+    self.sync_mailbox(
+      Bitboard::from(undo.from)
+        | Bitboard::from(undo.to)
+        | undo.captured_mask
+        | undo.rook_from
+        | undo.rook_to,
     );
 
-    self.clock += (move_mask > 0 && !self.white_turn) as u64;
+    self.hash = undo.hash;
+    self.history.pop();
+    self.en_passant = undo.en_passant;
+    self.half_clock = undo.half_clock;
+    self.white_short_castle = undo.white_short_castle;
+    self.white_long_castle = undo.white_long_castle;
+    self.black_short_castle = undo.black_short_castle;
+    self.black_long_castle = undo.black_long_castle;
+    self.cached_moves = CachedPieceMoves::default();
   }
 
-  pub fn update_cache(&mut self, from_mask: u64) {
-    if from_mask != self.cached_moves.from_mask {
-      self.cached_moves = MoveGen::cached(&self, from_mask);
-    }
+  //the target square if the move is legal including its promotion, empty otherwise
+  fn legal_move_mask(&mut self, input: MoveInput) -> Bitboard {
+    self.update_cache(input.from);
+    let to_mask = Bitboard::from(input.to);
+
+    //a pawn reaching the last rank has to name what it promotes to
+    let pawn_moves = self.cached_moves.pawn_default
+      | self.cached_moves.pawn_advance
+      | self.cached_moves.pawn_capture;
+    let promoting = to_mask.intersects(pawn_moves & PROMOTING_SQUARES);
+    let promotion_valid = match input.promotion {
+      Some(kind) => promoting && kind.is_promotion(),
+      None => !promoting,
+    };
+
+    (to_mask & self.cached_moves.all()).only_if(promotion_valid)
   }
 
-  fn handle_en_passant(&mut self, move_mask: u64) {
-    let en_passanted = mask_from_bool(move_mask & self.en_passant_mask > 0);
-    let en_passanted_pawn = en_passanted
-      & if_bool(
-        self.white_turn,
-        move_mask.move_down_mask(1),
-        move_mask.move_up_mask(1),
-      );
+  fn update_clocks(&mut self, move_mask: Bitboard) {
+    let moved = !move_mask.is_empty();
+    let pawn_moved =
+      move_mask.intersects(self.cached_moves.pawn_default) || !self.en_passant.is_empty();
+    let capture_or_pawn =
+      mask_from_bool(pawn_moved || move_mask.intersects(self.cached_moves.capturing));
+    self.half_clock = if_bool(moved, !capture_or_pawn & (self.half_clock + 1), self.half_clock);
 
-    self.white.remove_piece(en_passanted_pawn);
-    self.black.remove_piece(en_passanted_pawn);
+    self.clock += (moved && !self.white_turn) as u64;
   }
 
-  fn handle_pawn_advance(&mut self, move_mask: u64) {
-    let pawn_advanced = move_mask & self.cached_moves.pawn_advance > 0;
+  //only the squares a move touched need to be looked up again
+  fn sync_mailbox(&mut self, changed: Bitboard) {
+    for square in changed {
+      let white = self.white.kind_at(square.into()).map(|kind| (Color::White, kind));
+      let black = self.black.kind_at(square.into()).map(|kind| (Color::Black, kind));
+      self.mailbox[square.index()] = white.or(black);
+    }
+  }
+
+  pub fn update_cache(&mut self, from: Square) {
+    if Bitboard::from(from) != self.cached_moves.from {
+      self.cached_moves = MoveGen::cached(self, from);
+    }
+  }
 
-    self.en_passant_mask = if_bool(
-      self.white_turn,
-      move_mask.move_down_mask(1),
-      move_mask.move_up_mask(1),
-    ) & mask_from_bool(pawn_advanced);
+  //the square behind the one moved to, seen from the side moving
+  fn behind(&self, move_mask: Bitboard) -> Bitboard {
+    move_mask.move_down_mask(1).only_if(self.white_turn)
+      | move_mask.move_up_mask(1).only_if(!self.white_turn)
   }
 
-  fn handle_castling(&mut self, from_mask: u64, move_mask: u64) {
-    let white_king_moved = self.white_turn && move_mask & self.cached_moves.king_default > 0;
-    let white_long_rook_moved = self.white_turn
-      && move_mask & self.cached_moves.rook > 0
-      && from_mask == 0x80_00_00_00_00_00_00_00;
-    let white_short_rook_moved = self.white_turn
-      && move_mask & self.cached_moves.rook > 0
-      && from_mask == 0x01_00_00_00_00_00_00_00;
+  fn handle_en_passant(&mut self, move_mask: Bitboard) {
+    let en_passanted = move_mask.intersects(self.cached_moves.pawn_capture & self.en_passant);
+    let en_passanted_pawn = self.behind(move_mask).only_if(en_passanted);
 
-    self.white_long_castle &= !(white_king_moved || white_long_rook_moved);
-    self.white_short_castle &= !(white_king_moved || white_short_rook_moved);
+    self.white.remove_piece(en_passanted_pawn);
+    self.black.remove_piece(en_passanted_pawn);
+  }
 
-    let black_king_moved = !self.white_turn && move_mask & self.cached_moves.king_default > 0;
-    let black_long_rook_moved = !self.white_turn
-      && move_mask & self.cached_moves.rook > 0
-      && from_mask == 0x00_00_00_00_00_00_00_80;
-    let black_short_rook_moved = !self.white_turn
-      && move_mask & self.cached_moves.rook > 0
-      && from_mask == 0x00_00_00_00_00_00_00_01;
+  fn handle_pawn_advance(&mut self, move_mask: Bitboard) {
+    let pawn_advanced = move_mask.intersects(self.cached_moves.pawn_advance);
 
-    self.black_long_castle &= !(black_king_moved || black_long_rook_moved);
-    self.black_short_castle &= !(black_king_moved || black_short_rook_moved);
+    self.en_passant = self.behind(move_mask).only_if(pawn_advanced);
   }
 
-  fn handle_move(&mut self, from_mask: u64, move_mask: u64, promotion_choice: u64) {
+  fn handle_castling(&mut self, from_mask: Bitboard, move_mask: Bitboard) {
+    let short_castled = move_mask.intersects(self.cached_moves.king_short_castle);
+    let long_castled = move_mask.intersects(self.cached_moves.king_long_castle);
+    let ally_rooks = Bitboard(0x81_00_00_00_00_00_00_00).only_if(self.white_turn)
+      | Bitboard(0x81).only_if(!self.white_turn);
+
+    let short_rook = (ally_rooks & Bitboard(0x01_00_00_00_00_00_00_01)).only_if(short_castled);
+    let long_rook = (ally_rooks & Bitboard(0x80_00_00_00_00_00_00_80)).only_if(long_castled);
+    self.white.move_piece(short_rook, short_rook.move_left_mask(2));
+    self.black.move_piece(short_rook, short_rook.move_left_mask(2));
+    self.white.move_piece(long_rook, long_rook.move_right_mask(3));
+    self.black.move_piece(long_rook, long_rook.move_right_mask(3));
+
+    let king_moves = self.cached_moves.king_default
+      | self.cached_moves.king_short_castle
+      | self.cached_moves.king_long_castle;
+    let king_moved = move_mask.intersects(king_moves);
+    let white_king_moved = self.white_turn && king_moved;
+    let black_king_moved = !self.white_turn && king_moved;
+
+    //a rook leaving or being captured on its corner loses the right as well
+    let touched = from_mask.only_if(!move_mask.is_empty()) | move_mask;
+
+    let untouched = |corner: u64| !touched.intersects(Bitboard(corner));
+
+    self.white_long_castle &= !white_king_moved && untouched(0x80_00_00_00_00_00_00_00);
+    self.white_short_castle &= !white_king_moved && untouched(0x01_00_00_00_00_00_00_00);
+    self.black_long_castle &= !black_king_moved && untouched(0x00_00_00_00_00_00_00_80);
+    self.black_short_castle &= !black_king_moved && untouched(0x00_00_00_00_00_00_00_01);
+  }
+
+  fn handle_move(
+    &mut self,
+    from_mask: Bitboard,
+    move_mask: Bitboard,
+    promotion_choice: Option<PieceKind>,
+  ) {
     self.white.remove_piece(move_mask);
     self.black.remove_piece(move_mask);
 
@@ -270,80 +467,285 @@ impl Board {
 
     let pawn_moves =
       self.cached_moves.pawn_default | self.cached_moves.pawn_advance | self.cached_moves.capturing;
-    let promoting_mask = 0xFF_00_00_00_00_00_00_FF;
-    let promoted = pawn_moves & promoting_mask > 0;
-
-    let knight_promotion = mask_from_bool(promoted && promotion_choice == KNIGHT);
-    let bishop_promotion = mask_from_bool(promoted && promotion_choice == BISHOP);
-    let rook_promotion = mask_from_bool(promoted && promotion_choice == ROOK);
-    let queen_promotion = mask_from_bool(promoted && promotion_choice == QUEEN);
-
-    self.white.promote_to_knight(knight_promotion & move_mask);
-    self.white.promote_to_bishop(bishop_promotion & move_mask);
-    self.white.promote_to_rook(rook_promotion & move_mask);
-    self.white.promote_to_queen(queen_promotion & move_mask);
-    self.black.promote_to_knight(knight_promotion & move_mask);
-    self.black.promote_to_bishop(bishop_promotion & move_mask);
-    self.black.promote_to_rook(rook_promotion & move_mask);
-    self.black.promote_to_queen(queen_promotion & move_mask);
+    let promoted = pawn_moves.intersects(PROMOTING_SQUARES);
+
+    for kind in PieceKind::PROMOTIONS {
+      let chosen = move_mask.only_if(promoted && promotion_choice == Some(kind));
+      self.white.promote(kind, chosen);
+      self.black.promote(kind, chosen);
+    }
   }
 }
 
 //state
 impl Board {
-  pub(crate) fn pawns(&self) -> u64 {
+  pub(crate) fn pawns(&self) -> Bitboard {
     self.white.pawns | self.black.pawns
   }
 
-  pub(crate) fn knights(&self) -> u64 {
+  pub(crate) fn knights(&self) -> Bitboard {
     self.white.knights | self.black.knights
   }
 
-  pub(crate) fn bishops(&self) -> u64 {
+  pub(crate) fn bishops(&self) -> Bitboard {
     self.white.bishops | self.black.bishops
   }
 
-  pub(crate) fn rooks(&self) -> u64 {
+  pub(crate) fn rooks(&self) -> Bitboard {
     self.white.rooks | self.black.rooks
   }
 
-  pub(crate) fn queens(&self) -> u64 {
+  pub(crate) fn queens(&self) -> Bitboard {
     self.white.queens | self.black.queens
   }
 
-  pub(crate) fn kings(&self) -> u64 {
+  pub(crate) fn kings(&self) -> Bitboard {
     self.white.king | self.black.king
   }
 
-  pub fn get_status(&self) -> u64 {
-    let mut movegen = MoveGen::default(&self);
-    let piece_status = movegen.get_status();
-    let fifty_move = mask_from_bool(self.half_clock > 100);
+  pub fn en_passant(&self) -> Option<Square> {
+    self.en_passant.first()
+  }
+
+  pub fn hash(&self) -> u64 {
+    self.hash
+  }
+
+  pub fn side_to_move(&self) -> Color {
+    if self.white_turn { Color::White } else { Color::Black }
+  }
+
+  pub fn white(&self) -> &Pieces {
+    &self.white
+  }
+
+  pub fn black(&self) -> &Pieces {
+    &self.black
+  }
+
+  pub fn pieces(&self, color: Color) -> &Pieces {
+    match color {
+      Color::White => &self.white,
+      Color::Black => &self.black,
+    }
+  }
+
+  pub fn fullmove_number(&self) -> u64 {
+    self.clock
+  }
+
+  pub fn halfmove_clock(&self) -> u64 {
+    self.half_clock
+  }
+
+  pub fn legal_moves(&self) -> MoveList {
+    MoveGen::legal(self)
+  }
+
+  pub fn legal_captures(&self) -> MoveList {
+    MoveGen::captures(self)
+  }
+
+  //times the current position occurred,
+  //only positions since the last capture or pawn move can repeat
+  pub fn repetitions(&self) -> u64 {
+    let reversible = (self.half_clock as usize).min(self.history.len());
+    let previous = self.history[self.history.len() - reversible..]
+      .iter()
+      .filter(|&&hash| hash == self.hash)
+      .count();
+
+    previous as u64 + 1
+  }
+
+  pub fn in_check(&self) -> bool {
+    MoveGen::default(self).in_check()
+  }
+
+  //only endings the rules enforce on their own, claimable draws are reported by claimable_draw
+  pub fn get_status(&self) -> GameStatus {
+    //a checkmate delivered on the last move still stands
+    if self.legal_moves().is_empty() {
+      return if self.in_check() {
+        GameStatus::Checkmate {
+          winner: !self.side_to_move(),
+        }
+      } else {
+        GameStatus::Stalemate
+      };
+    }
+
+    if self.insufficient_material() {
+      GameStatus::InsufficientMaterial
+    } else if self.half_clock >= 150 {
+      GameStatus::FiftyMove
+    } else if self.repetitions() >= 5 {
+      GameStatus::Repetition
+    } else {
+      GameStatus::Playing
+    }
+  }
+
+  //dead position, no sequence of legal moves can end in checkmate for either side
+  pub fn insufficient_material(&self) -> bool {
+    let heavy = self.pawns() | self.rooks() | self.queens();
+    let lone_knight = self.knights().count() == 1 && self.bishops().is_empty();
+    let bishops_only = self.knights().is_empty() && same_colored(self.bishops());
+
+    heavy.is_empty() && (lone_knight || bishops_only)
+  }
+
+  //whether the side can never checkmate, so their opponent running out of time is a draw
+  pub fn insufficient_material_to_win(&self, color: Color) -> bool {
+    let ally = self.pieces(color);
+    let enemy = self.pieces(!color);
+    let ally_others = ally.pieces_concat() & !ally.king;
+    let enemy_others = enemy.pieces_concat() & !enemy.king;
+    if ally_others.is_empty() {
+      return true;
+    }
+    if !(ally.pawns | ally.rooks | ally.queens).is_empty() {
+      return false;
+    }
+
+    //any other enemy piece can block the king in for a helpmate
+    let lone_knight =
+      ally.knights.count() == 1 && ally.bishops.is_empty() && enemy_others.is_empty();
+    let bishops_only = ally.knights.is_empty()
+      && enemy_others == enemy.bishops
+      && same_colored(ally.bishops | enemy.bishops);
 
-    let result = (fifty_move & DRAW) | (!fifty_move & piece_status);
-    result
+    lone_knight || bishops_only
   }
 
-  pub fn is_empty(&self, at_mask: u64) -> bool {
-    self.white.is_empty(at_mask) && self.black.is_empty(at_mask)
+  //the status once the flagged side runs out of time
+  pub fn timeout(&self, flagged: Color) -> GameStatus {
+    if self.insufficient_material_to_win(!flagged) {
+      GameStatus::InsufficientMaterial
+    } else {
+      GameStatus::Timeout { winner: !flagged }
+    }
   }
 
-  pub fn is_white(&self, at_mask: u64) -> bool {
-    self.white.pieces_concat() & at_mask > 0
+  //threefold repetition or the fifty-move rule, the game goes on until a player claims it
+  pub fn claimable_draw(&self) -> Option<GameStatus> {
+    if self.get_status().is_over() {
+      None
+    } else if self.half_clock >= 100 {
+      Some(GameStatus::FiftyMove)
+    } else if self.repetitions() >= 3 {
+      Some(GameStatus::Repetition)
+    } else {
+      None
+    }
   }
 
-  pub fn is_promotion(&self, to_mask: u64) -> bool {
+  pub fn to_fen(&self) -> String {
+    let mut fen = String::new();
+
+    //pieces
+    for row_id in 0..8 {
+      let mut empty = 0;
+      for column in 0..8 {
+        let pos = Bitboard(0x80).move_right_mask(column).move_down_mask(row_id);
+        let piece = match (self.white.kind_at(pos), self.black.kind_at(pos)) {
+          (Some(kind), _) => kind.letter(),
+          (_, Some(kind)) => kind.letter().to_ascii_lowercase(),
+          _ => {
+            empty += 1;
+            continue;
+          }
+        };
+
+        if empty > 0 {
+          fen.push_str(&empty.to_string());
+          empty = 0;
+        }
+        fen.push(piece);
+      }
+
+      if empty > 0 {
+        fen.push_str(&empty.to_string());
+      }
+      if row_id < 7 {
+        fen.push('/');
+      }
+    }
+
+    //turn
+    fen.push_str(if self.white_turn { " w " } else { " b " });
+
+    //castling
+    let rights = [
+      (self.white_short_castle, 'K'),
+      (self.white_long_castle, 'Q'),
+      (self.black_short_castle, 'k'),
+      (self.black_long_castle, 'q'),
+    ];
+    let castling: String = rights
+      .iter()
+      .filter(|(right, _)| *right)
+      .map(|(_, c)| c)
+      .collect();
+    fen.push_str(if castling.is_empty() { "-" } else { &castling });
+
+    //en passant
+    match self.en_passant() {
+      Some(square) => fen.push_str(&format!(" {square}")),
+      None => fen.push_str(" -"),
+    }
+
+    //clocks
+    fen.push_str(&format!(" {} {}", self.half_clock, self.clock));
+    fen
+  }
+
+  pub fn occupancy(&self) -> Bitboard {
+    self.white.pieces_concat() | self.black.pieces_concat()
+  }
+
+  //pieces of both colours attacking the square,
+  //pieces missing from occupancy neither attack nor block
+  pub fn attackers_to(&self, at: Square, occupancy: Bitboard) -> Bitboard {
+    let (white, black) = (&self.white, &self.black);
+    let diagonal = white.bishops | white.queens | black.bishops | black.queens;
+    let straight = white.rooks | white.queens | black.rooks | black.queens;
+
+    //the attack tables work on raw masks
+    let (mask, occupied) = (at.mask(), occupancy.0);
+    let attackers = Bitboard(pawn_attacks(Color::Black, mask)) & white.pawns
+      | Bitboard(pawn_attacks(Color::White, mask)) & black.pawns
+      | Bitboard(knight_attacks(mask)) & (white.knights | black.knights)
+      | Bitboard(king_attacks(mask)) & (white.king | black.king)
+      | Bitboard(bishop_attacks(at.index(), occupied)) & diagonal
+      | Bitboard(rook_attacks(at.index(), occupied)) & straight;
+    attackers & occupancy
+  }
+
+  pub fn piece_at(&self, at: Square) -> Option<(Color, PieceKind)> {
+    self.mailbox[at.index()]
+  }
+
+  pub fn is_empty(&self, at: Square) -> bool {
+    self.white.is_empty(at.into()) && self.black.is_empty(at.into())
+  }
+
+  pub fn is_white(&self, at: Square) -> bool {
+    self.white.pieces_concat().contains(at)
+  }
+
+  pub fn is_promotion(&self, to: Square) -> bool {
     let pawn_moves =
       self.cached_moves.pawn_default | self.cached_moves.pawn_advance | self.cached_moves.capturing;
-    let promoting_mask = 0xFF_00_00_00_00_00_00_FF;
-    pawn_moves & promoting_mask & to_mask > 0
+    (pawn_moves & PROMOTING_SQUARES).contains(to)
   }
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
   mod constructors {
-    use crate::board::{Board, status::PLAYING};
+    use crate::board::{Board, status::GameStatus};
 
     #[test]
     fn default() {
@@ -363,11 +765,11 @@ mod tests {
       assert_eq!(board.black.king, 0x00_00_00_00_00_00_00_08);
 
       assert_eq!(board.white_turn, true);
-      assert_eq!(board.get_status(), PLAYING);
+      assert_eq!(board.get_status(), GameStatus::Playing);
       assert_eq!(board.clock, 1);
       assert_eq!(board.half_clock, 0);
 
-      assert_eq!(board.en_passant_mask, 0x00_00_00_00_00_00_00_00);
+      assert_eq!(board.en_passant, 0x00_00_00_00_00_00_00_00);
       assert_eq!(board.white_long_castle, true);
       assert_eq!(board.white_short_castle, true);
       assert_eq!(board.black_long_castle, true);
@@ -397,7 +799,7 @@ mod tests {
       assert_eq!(a.clock, b.clock);
       assert_eq!(a.half_clock, b.half_clock);
 
-      assert_eq!(a.en_passant_mask, b.en_passant_mask);
+      assert_eq!(a.en_passant, b.en_passant);
       assert_eq!(a.white_long_castle, b.white_long_castle);
       assert_eq!(a.white_short_castle, b.white_short_castle);
       assert_eq!(a.black_long_castle, b.black_long_castle);
@@ -417,26 +819,175 @@ mod tests {
     }
   }
 
+  mod fen_errors {
+    use crate::board::{Board, fen_error::FenError};
+
+    const PIECES: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR";
+
+    fn error(fen: &str) -> FenError {
+      Board::try_from_fen(fen).err().unwrap()
+    }
+
+    #[test]
+    fn char_positions() {
+      //the no-break space separating the fields takes two bytes
+      assert_eq!(error("4k3/8/8/8/8/8/8/4K3\u{a0}x - - 0 1"), FenError::SideToMove(20));
+      assert_eq!(error("4k3/8/8/8/8/8/8/4K3 w Ké - 0 1"), FenError::Castling(23));
+      assert_eq!(error("ü"), FenError::FieldCount(1));
+    }
+
+    #[test]
+    fn field_count() {
+      assert_eq!(error(PIECES), FenError::FieldCount(43));
+      assert_eq!(error(""), FenError::FieldCount(0));
+    }
+
+    #[test]
+    fn placement() {
+      assert_eq!(
+        error("rnbqkbnr/pppppppp/8/8/7/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"),
+        FenError::Placement(23)
+      );
+      assert_eq!(
+        error("rnbqkbnr/ppppxppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"),
+        FenError::Placement(13)
+      );
+      assert_eq!(
+        error("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR/8 w KQkq - 0 1"),
+        FenError::Placement(43)
+      );
+      assert_eq!(
+        error("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP w KQkq - 0 1"),
+        FenError::Placement(34)
+      );
+      assert_eq!(
+        error("rnbqkbnr/pppppppp/9/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"),
+        FenError::Placement(18)
+      );
+      assert_eq!(
+        error("rnbqkbnrr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"),
+        FenError::Placement(8)
+      );
+    }
+
+    #[test]
+    fn side_to_move() {
+      assert_eq!(error(&format!("{PIECES} x KQkq - 0 1")), FenError::SideToMove(44));
+      assert_eq!(error(&format!("{PIECES} wb KQkq - 0 1")), FenError::SideToMove(44));
+    }
+
+    #[test]
+    fn castling() {
+      assert_eq!(error(&format!("{PIECES} w KQkx - 0 1")), FenError::Castling(49));
+      assert_eq!(error(&format!("{PIECES} w KK - 0 1")), FenError::Castling(47));
+    }
+
+    #[test]
+    fn en_passant() {
+      assert_eq!(error(&format!("{PIECES} w KQkq z3 0 1")), FenError::EnPassant(51));
+      assert_eq!(error(&format!("{PIECES} w KQkq e9 0 1")), FenError::EnPassant(52));
+      assert_eq!(error(&format!("{PIECES} w KQkq e3e 0 1")), FenError::EnPassant(51));
+    }
+
+    #[test]
+    fn clocks() {
+      assert_eq!(error(&format!("{PIECES} w KQkq - a 1")), FenError::Halfmove(53));
+      assert_eq!(error(&format!("{PIECES} w KQkq - 0 1x")), FenError::Fullmove(56));
+    }
+
+    #[test]
+    fn clock_values() {
+      assert_eq!(error(&format!("{PIECES} w KQkq - 0 0")), FenError::Fullmove(55));
+      //a stale clock next to an en passant square is only rejected by validation
+      assert!(Board::try_from_fen("4k3/8/8/8/3pP3/8/8/4K3 b - e3 3 1").is_ok());
+      assert_eq!(
+        error(&format!("{PIECES} w KQkq - 99999999999999999999 1")),
+        FenError::Halfmove(53)
+      );
+    }
+
+    #[test]
+    fn display() {
+      assert_eq!(
+        error(&format!("{PIECES} x KQkq - 0 1")).to_string(),
+        "Invalid active color data at character 44"
+      );
+    }
+
+    #[test]
+    fn valid() {
+      assert!(Board::try_from_fen(&format!("{PIECES} w KQkq - 0 1")).is_ok());
+      assert!(Board::try_from_fen("8/8/8/2k5/2pP4/8/B7/4K3 b - d3 0 3").is_ok());
+    }
+  }
+
   mod clock {
-    use crate::board::{Board, move_input::MoveInput};
+    use crate::board::{
+      Board,
+      move_input::MoveInput,
+      status::GameStatus,
+    };
+
+    #[test]
+    fn from_fen() {
+      let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 b - - 37 42");
+      assert_eq!(board.half_clock, 37);
+      assert_eq!(board.clock, 42);
+    }
+
+    #[test]
+    fn from_fen_clock_advances() {
+      let mut board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 b - - 37 42");
+      assert!(board.move_piece(MoveInput::from_id(3, 4).unwrap()));
+      assert_eq!(board.half_clock, 38);
+      assert_eq!(board.clock, 43);
+    }
+
+    #[test]
+    fn fifty_move_boundary() {
+      let mut board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 99 80");
+      assert_eq!(board.get_status(), GameStatus::Playing);
+      assert!(board.move_piece(MoveInput::from_id(63, 55).unwrap()));
+      assert_eq!(board.claimable_draw(), Some(GameStatus::FiftyMove));
+
+      let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 100 80");
+      assert_eq!(board.claimable_draw(), Some(GameStatus::FiftyMove));
+    }
+
+    #[test]
+    fn fifty_move_reset_by_pawn() {
+      let mut board = Board::from_fen("4k3/8/8/8/8/8/P7/4K3 w - - 99 80");
+      assert!(board.move_piece(MoveInput::from_id(55, 47).unwrap()));
+      assert_eq!(board.half_clock, 0);
+      assert_eq!(board.get_status(), GameStatus::Playing);
+    }
+
+    #[test]
+    fn seventy_five_move_boundary() {
+      let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 149 100");
+      assert_eq!(board.claimable_draw(), Some(GameStatus::FiftyMove));
+
+      let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 150 100");
+      assert_eq!(board.get_status(), GameStatus::FiftyMove);
+    }
 
     #[test]
     fn full_clock() {
       let mut board = Board::default();
       assert_eq!(board.clock, 1);
 
-      assert_eq!(board.move_piece(MoveInput::from_id(52, 36)), true);
+      assert_eq!(board.move_piece(MoveInput::from_id(52, 36).unwrap()), true);
       assert_eq!(board.clock, 1);
-      assert_eq!(board.move_piece(MoveInput::from_id(12, 4)), false);
+      assert_eq!(board.move_piece(MoveInput::from_id(12, 4).unwrap()), false);
       assert_eq!(board.clock, 1);
-      assert_eq!(board.move_piece(MoveInput::from_id(12, 28)), true);
+      assert_eq!(board.move_piece(MoveInput::from_id(12, 28).unwrap()), true);
       assert_eq!(board.clock, 2);
 
-      assert_eq!(board.move_piece(MoveInput::from_id(61, 34)), true);
+      assert_eq!(board.move_piece(MoveInput::from_id(61, 34).unwrap()), true);
       assert_eq!(board.clock, 2);
-      assert_eq!(board.move_piece(MoveInput::from_id(56, 57)), false);
+      assert_eq!(board.move_piece(MoveInput::from_id(56, 57).unwrap()), false);
       assert_eq!(board.clock, 2);
-      assert_eq!(board.move_piece(MoveInput::from_id(1, 18)), true);
+      assert_eq!(board.move_piece(MoveInput::from_id(1, 18).unwrap()), true);
       assert_eq!(board.clock, 3);
     }
 
@@ -445,26 +996,676 @@ mod tests {
       let mut board = Board::default();
       assert_eq!(board.half_clock, 0);
 
-      assert_eq!(board.move_piece(MoveInput::from_id(52, 36)), true);
+      assert_eq!(board.move_piece(MoveInput::from_id(52, 36).unwrap()), true);
       assert_eq!(board.half_clock, 0);
-      assert_eq!(board.move_piece(MoveInput::from_id(12, 4)), false);
+      assert_eq!(board.move_piece(MoveInput::from_id(12, 4).unwrap()), false);
       assert_eq!(board.half_clock, 0);
-      assert_eq!(board.move_piece(MoveInput::from_id(12, 28)), true);
+      assert_eq!(board.move_piece(MoveInput::from_id(12, 28).unwrap()), true);
       assert_eq!(board.half_clock, 0);
 
-      assert_eq!(board.move_piece(MoveInput::from_id(61, 34)), true);
+      assert_eq!(board.move_piece(MoveInput::from_id(61, 34).unwrap()), true);
       assert_eq!(board.half_clock, 1);
-      assert_eq!(board.move_piece(MoveInput::from_id(56, 57)), false);
+      assert_eq!(board.move_piece(MoveInput::from_id(56, 57).unwrap()), false);
       assert_eq!(board.half_clock, 1);
-      assert_eq!(board.move_piece(MoveInput::from_id(1, 18)), true);
+      assert_eq!(board.move_piece(MoveInput::from_id(1, 18).unwrap()), true);
       assert_eq!(board.half_clock, 2);
 
-      assert_eq!(board.move_piece(MoveInput::from_id(34, 20)), true);
+      assert_eq!(board.move_piece(MoveInput::from_id(34, 20).unwrap()), true);
       assert_eq!(board.half_clock, 3);
-      assert_eq!(board.move_piece(MoveInput::from_id(55, 47)), false);
+      assert_eq!(board.move_piece(MoveInput::from_id(55, 47).unwrap()), false);
       assert_eq!(board.half_clock, 3);
-      assert_eq!(board.move_piece(MoveInput::from_id(4, 20)), true);
+      assert_eq!(board.move_piece(MoveInput::from_id(4, 20).unwrap()), true);
       assert_eq!(board.half_clock, 0);
     }
   }
+
+  mod legal_moves {
+    use crate::board::{Board, move_input::MoveInput, move_list::Move};
+
+    fn find(moves: &[Move], from: usize, to: usize) -> Option<Move> {
+      moves
+        .iter()
+        .find(|m| m.from.index() == from && m.to.index() == to)
+        .copied()
+    }
+
+    #[test]
+    fn default() {
+      let moves = Board::default().legal_moves();
+
+      assert_eq!(moves.len(), 20);
+      assert_eq!(moves.iter().filter(|m| m.is_double_push()).count(), 8);
+      assert_eq!(moves.iter().filter(|m| m.is_capture()).count(), 0);
+    }
+
+    #[test]
+    fn kiwipete() {
+      let board =
+        Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1");
+      let moves = board.legal_moves();
+
+      assert_eq!(moves.len(), 48);
+      assert_eq!(moves.iter().filter(|m| m.is_capture()).count(), 8);
+      assert_eq!(moves.iter().filter(|m| m.is_castle()).count(), 2);
+    }
+
+    #[test]
+    fn promotion() {
+      let board = Board::from_fen("8/P6k/8/8/8/8/8/K7 w - - 0 1");
+      let moves = board.legal_moves();
+
+      assert_eq!(moves.len(), 7);
+      assert_eq!(moves.iter().filter(|m| m.promotion.is_some()).count(), 4);
+    }
+
+    #[test]
+    fn captures() {
+      assert!(Board::default().legal_captures().is_empty());
+
+      let board =
+        Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1");
+      let captures = board.legal_captures();
+      assert_eq!(captures.len(), 8);
+      assert!(captures.iter().all(|m| m.is_capture()));
+
+      //quiet promotions are tactical too
+      let board = Board::from_fen("1n5k/P7/8/8/8/8/8/K7 w - - 0 1");
+      let captures = board.legal_captures();
+      assert_eq!(captures.len(), 8);
+      assert_eq!(captures.iter().filter(|m| m.is_capture()).count(), 4);
+    }
+
+    #[test]
+    fn en_passant_capture() {
+      let mut board = Board::from_fen("k7/2p5/8/1P6/8/8/8/K7 b - - 0 1");
+      assert!(board.move_piece(MoveInput::from_id(13, 29).unwrap()));
+
+      let captures = board.legal_captures();
+      assert_eq!(captures.len(), 1);
+      assert!(captures[0].is_en_passant());
+    }
+
+    #[test]
+    fn en_passant() {
+      let mut board = Board::from_fen("k7/2p5/8/1P6/8/8/8/K7 b - - 0 1");
+      assert!(board.move_piece(MoveInput::from_id(13, 29).unwrap()));
+
+      let moves = board.legal_moves();
+      let en_passant = find(&moves, 30, 21).unwrap();
+      assert!(en_passant.is_en_passant());
+      assert!(en_passant.is_capture());
+    }
+
+    #[test]
+    fn en_passant_pinned_on_rank() {
+      let mut board = Board::from_fen("7k/2p5/8/KP5r/8/8/8/8 b - - 0 1");
+      assert!(board.move_piece(MoveInput::from_id(13, 29).unwrap()));
+
+      let moves = board.legal_moves();
+      assert_eq!(find(&moves, 30, 21), None);
+    }
+
+    #[test]
+    fn en_passant_resolves_check() {
+      let mut board = Board::from_fen("8/8/8/5k2/3p4/8/4P3/K7 w - - 0 1");
+      assert!(board.move_piece(MoveInput::from_id(51, 35).unwrap()));
+
+      let moves = board.legal_moves();
+      assert!(find(&moves, 36, 43).unwrap().is_en_passant());
+    }
+
+    #[test]
+    fn double_check() {
+      let board = Board::from_fen("4r2k/8/8/8/8/3n4/8/RB2K3 w - - 0 1");
+      let king = board.white.king;
+
+      let moves = board.legal_moves();
+      assert!(!moves.is_empty());
+      assert!(moves.iter().all(|m| king.contains(m.from)));
+    }
+
+    #[test]
+    fn king_defended_capture() {
+      let board = Board::from_fen("4k3/8/8/8/8/8/r2r4/4K3 w - - 0 1");
+      let moves = board.legal_moves();
+
+      assert_eq!(moves.len(), 1);
+      assert_eq!(moves[0].to.index(), 58);
+    }
+  }
+
+  mod make_unmake {
+    use crate::board::{Board, move_input::MoveInput};
+
+    fn assert_same(a: &Board, b: &Board) {
+      assert_eq!(a.white, b.white);
+      assert_eq!(a.black, b.black);
+      assert_eq!(a.white_turn, b.white_turn);
+      assert_eq!(a.clock, b.clock);
+      assert_eq!(a.half_clock, b.half_clock);
+      assert_eq!(a.en_passant, b.en_passant);
+      assert_eq!(a.white_short_castle, b.white_short_castle);
+      assert_eq!(a.white_long_castle, b.white_long_castle);
+      assert_eq!(a.black_short_castle, b.black_short_castle);
+      assert_eq!(a.black_long_castle, b.black_long_castle);
+      assert_eq!(a.hash, b.hash);
+      assert_eq!(a.history, b.history);
+      assert_eq!(a.mailbox, b.mailbox);
+    }
+
+    fn assert_identity(board: &mut Board, depth: u32) {
+      if depth == 0 {
+        return;
+      }
+
+      for m in board.legal_moves() {
+        let before = board.clone();
+        let undo = board.make_move(m.input()).unwrap();
+        assert_identity(board, depth - 1);
+        board.unmake_move(undo);
+        assert_same(board, &before);
+      }
+    }
+
+    #[test]
+    fn default() {
+      assert_identity(&mut Board::default(), 3);
+    }
+
+    #[test]
+    fn kiwipete() {
+      let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+      assert_identity(&mut Board::from_fen(fen), 2);
+    }
+
+    #[test]
+    fn en_passant() {
+      assert_identity(&mut Board::from_fen("8/8/1k6/2b5/2pP4/8/5K2/8 b - d3 0 1"), 3);
+    }
+
+    #[test]
+    fn promotion() {
+      let fen = "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1";
+      assert_identity(&mut Board::from_fen(fen), 2);
+    }
+
+    #[test]
+    fn illegal_move() {
+      let mut board = Board::default();
+      assert!(board.make_move(MoveInput::from_id(52, 28).unwrap()).is_none());
+      assert!(board.make_move(MoveInput::from_id(12, 28).unwrap()).is_none());
+    }
+
+    #[test]
+    fn promotion_choice() {
+      let mut board = Board::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1");
+      assert!(board.make_move(MoveInput::from_uci("a7a8").unwrap()).is_none());
+      assert!(board.make_move(MoveInput::from_uci("e1e2q").unwrap()).is_none());
+      assert_eq!(board.to_fen(), "4k3/P7/8/8/8/8/8/4K3 w - - 0 1");
+
+      let undo = board.make_move(MoveInput::from_uci("a7a8q").unwrap()).unwrap();
+      assert_eq!(board.to_fen(), "Q3k3/8/8/8/8/8/8/4K3 b - - 0 1");
+      board.unmake_move(undo);
+      assert_eq!(board.to_fen(), "4k3/P7/8/8/8/8/8/4K3 w - - 0 1");
+    }
+  }
+
+  mod mailbox {
+    use crate::board::{
+      Board,
+      bitboard::Bitboard,
+      move_input::MoveInput,
+      piece::{Color, PieceKind},
+    };
+
+    fn assert_synced(board: &Board) {
+      for square in Bitboard::FULL {
+        let white = board.white.kind_at(square.into()).map(|kind| (Color::White, kind));
+        let black = board.black.kind_at(square.into()).map(|kind| (Color::Black, kind));
+        assert_eq!(board.piece_at(square), white.or(black), "{square}");
+      }
+    }
+
+    fn walk(board: &mut Board, depth: u32) {
+      assert_synced(board);
+      if depth == 0 {
+        return;
+      }
+
+      for m in board.legal_moves() {
+        let undo = board.make_move(m.input()).unwrap();
+        walk(board, depth - 1);
+        board.unmake_move(undo);
+      }
+      assert_synced(board);
+    }
+
+    #[test]
+    fn default() {
+      let board = Board::default();
+      assert_eq!(
+        board.piece_at("e1".parse().unwrap()),
+        Some((Color::White, PieceKind::King))
+      );
+      assert_eq!(
+        board.piece_at("d8".parse().unwrap()),
+        Some((Color::Black, PieceKind::Queen))
+      );
+      assert_eq!(board.piece_at("e4".parse().unwrap()), None);
+      assert_synced(&board);
+      assert_synced(&Board::empty());
+    }
+
+    #[test]
+    fn move_piece() {
+      let mut board = Board::default();
+      assert!(board.move_piece(MoveInput::from_uci("e2e4").unwrap()));
+      assert_eq!(board.piece_at("e2".parse().unwrap()), None);
+      assert_eq!(
+        board.piece_at("e4".parse().unwrap()),
+        Some((Color::White, PieceKind::Pawn))
+      );
+
+      assert!(!board.move_piece(MoveInput::from_uci("e4e5").unwrap()));
+      assert_synced(&board);
+    }
+
+    #[test]
+    fn special_moves() {
+      let mut board = Board::from_fen("r3k3/6P1/8/3pP3/8/8/8/R3K2R w KQq d6 0 1");
+      for uci in ["e5d6", "e8c8", "g7g8n", "c8b8", "e1g1"] {
+        assert!(board.move_piece(MoveInput::from_uci(uci).unwrap()), "{uci}");
+        assert_synced(&board);
+      }
+      assert_eq!(
+        board.piece_at("g8".parse().unwrap()),
+        Some((Color::White, PieceKind::Knight))
+      );
+      assert_eq!(
+        board.piece_at("f1".parse().unwrap()),
+        Some((Color::White, PieceKind::Rook))
+      );
+      assert_eq!(
+        board.piece_at("d8".parse().unwrap()),
+        Some((Color::Black, PieceKind::Rook))
+      );
+      assert_eq!(board.piece_at("d5".parse().unwrap()), None);
+    }
+
+    #[test]
+    fn make_unmake() {
+      let mut board =
+        Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1");
+      walk(&mut board, 2);
+
+      let mut board = Board::from_fen("n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1");
+      walk(&mut board, 2);
+    }
+  }
+
+  mod attackers {
+    use crate::board::{Board, bitboard::Bitboard, square::Square};
+
+    fn square(name: &str) -> Square {
+      name.parse().unwrap()
+    }
+
+    fn squares(names: &[&str]) -> Bitboard {
+      names.iter().map(|name| square(name)).collect()
+    }
+
+    #[test]
+    fn every_piece_kind() {
+      let board = Board::from_fen("4k3/5b2/1n6/3p4/4PN2/1Q6/8/3RK3 w - - 0 1");
+      let attackers = board.attackers_to(square("d5"), board.occupancy());
+      assert_eq!(attackers, squares(&["b6", "f7", "e4", "f4", "b3", "d1"]));
+
+      let attackers = board.attackers_to(square("e4"), board.occupancy());
+      assert_eq!(attackers, squares(&["d5"]));
+      //the pawn attacks diagonally forward only
+      assert!(!board.attackers_to(square("e5"), board.occupancy()).contains(square("e4")));
+    }
+
+    #[test]
+    fn x_rays() {
+      let board = Board::from_fen("3rk3/8/8/3p4/8/8/3R4/3RK3 w - - 0 1");
+      let occupancy = board.occupancy();
+      assert_eq!(board.attackers_to(square("d5"), occupancy), squares(&["d2", "d8"]));
+
+      //the rook behind joins in once the one in front has left
+      let occupancy = occupancy ^ Bitboard::from(square("d2"));
+      assert_eq!(board.attackers_to(square("d5"), occupancy), squares(&["d1", "d8"]));
+    }
+  }
+
+  mod hashing {
+    use crate::board::{Board, move_input::MoveInput, zobrist};
+
+    fn assert_incremental(board: &mut Board, depth: u32) {
+      assert_eq!(board.hash(), zobrist::hash(board));
+      if depth == 0 {
+        return;
+      }
+
+      for m in board.legal_moves() {
+        let undo = board.make_move(m.input()).unwrap();
+        assert_incremental(board, depth - 1);
+        board.unmake_move(undo);
+      }
+    }
+
+    #[test]
+    fn incremental_matches_full() {
+      assert_incremental(&mut Board::default(), 3);
+
+      let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+      assert_incremental(&mut Board::from_fen(fen), 2);
+    }
+
+    #[test]
+    fn fen_matches_default() {
+      let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+      assert_eq!(Board::default().hash(), Board::from_fen(fen).hash());
+    }
+
+    #[test]
+    fn transposition() {
+      let mut a = Board::default();
+      assert!(a.move_piece(MoveInput::from_id(57, 42).unwrap()));
+      assert!(a.move_piece(MoveInput::from_id(6, 21).unwrap()));
+      assert!(a.move_piece(MoveInput::from_id(62, 45).unwrap()));
+
+      let mut b = Board::default();
+      assert!(b.move_piece(MoveInput::from_id(62, 45).unwrap()));
+      assert!(b.move_piece(MoveInput::from_id(6, 21).unwrap()));
+      assert!(b.move_piece(MoveInput::from_id(57, 42).unwrap()));
+
+      assert_eq!(a.hash(), b.hash());
+      assert_ne!(a.hash(), Board::default().hash());
+    }
+
+    #[test]
+    fn side_castling_and_en_passant() {
+      let a = Board::from_fen("4k3/8/8/8/8/8/8/R3K2R w KQ - 0 1");
+      let b = Board::from_fen("4k3/8/8/8/8/8/8/R3K2R b KQ - 0 1");
+      let c = Board::from_fen("4k3/8/8/8/8/8/8/R3K2R w K - 0 1");
+      assert_ne!(a.hash(), b.hash());
+      assert_ne!(a.hash(), c.hash());
+
+      let capturable = Board::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1");
+      let no_ep = Board::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - - 0 1");
+      assert_ne!(capturable.hash(), no_ep.hash());
+
+      let uncapturable = Board::from_fen("4k3/8/8/3p4/8/8/8/4K3 w - d6 0 1");
+      let no_ep = Board::from_fen("4k3/8/8/3p4/8/8/8/4K3 w - - 0 1");
+      assert_eq!(uncapturable.hash(), no_ep.hash());
+    }
+  }
+
+  mod repetition {
+    use crate::board::{
+      Board,
+      move_input::MoveInput,
+      piece::Color,
+      status::GameStatus,
+    };
+
+    fn shuffle_knights(board: &mut Board) {
+      assert!(board.move_piece(MoveInput::from_id(57, 42).unwrap()));
+      assert!(board.move_piece(MoveInput::from_id(1, 18).unwrap()));
+      assert!(board.move_piece(MoveInput::from_id(42, 57).unwrap()));
+      assert!(board.move_piece(MoveInput::from_id(18, 1).unwrap()));
+    }
+
+    #[test]
+    fn threefold() {
+      let mut board = Board::default();
+      shuffle_knights(&mut board);
+      assert_eq!(board.repetitions(), 2);
+      assert_eq!(board.get_status(), GameStatus::Playing);
+
+      shuffle_knights(&mut board);
+      assert_eq!(board.repetitions(), 3);
+      assert_eq!(board.claimable_draw(), Some(GameStatus::Repetition));
+    }
+
+    #[test]
+    fn fivefold() {
+      let mut board = Board::default();
+      for _ in 0..3 {
+        shuffle_knights(&mut board);
+      }
+      assert_eq!(board.claimable_draw(), Some(GameStatus::Repetition));
+
+      shuffle_knights(&mut board);
+      assert_eq!(board.repetitions(), 5);
+      assert_eq!(board.get_status(), GameStatus::Repetition);
+    }
+
+    #[test]
+    fn pawn_move_resets_repetitions() {
+      let mut board = Board::default();
+      shuffle_knights(&mut board);
+      assert!(board.move_piece(MoveInput::from_id(52, 36).unwrap()));
+      assert!(board.move_piece(MoveInput::from_id(12, 28).unwrap()));
+      shuffle_knights(&mut board);
+      assert_eq!(board.repetitions(), 2);
+    }
+
+    #[test]
+    fn unmake_pops_history() {
+      let mut board = Board::default();
+      shuffle_knights(&mut board);
+      let undo = board.make_move(MoveInput::from_id(57, 42).unwrap()).unwrap();
+      board.unmake_move(undo);
+      assert_eq!(board.repetitions(), 2);
+    }
+
+    #[test]
+    fn fifty_move() {
+      let mut board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1");
+      board.half_clock = 99;
+      assert_eq!(board.get_status(), GameStatus::Playing);
+
+      assert!(board.move_piece(MoveInput::from_id(63, 55).unwrap()));
+      assert_eq!(board.claimable_draw(), Some(GameStatus::FiftyMove));
+    }
+
+    #[test]
+    fn seventy_five_move() {
+      let mut board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1");
+      board.half_clock = 149;
+      assert!(board.move_piece(MoveInput::from_id(63, 55).unwrap()));
+      assert_eq!(board.get_status(), GameStatus::FiftyMove);
+    }
+
+    #[test]
+    fn seventy_five_move_checkmate() {
+      let mut board = Board::from_fen("4k3/R7/8/8/8/8/8/1R2K3 w - - 0 1");
+      board.half_clock = 149;
+      assert!(board.move_piece(MoveInput::from_id(62, 6).unwrap()));
+      assert_eq!(board.get_status(), GameStatus::Checkmate { winner: Color::White });
+    }
+  }
+
+  mod status {
+    use crate::board::{Board, move_input::MoveInput, piece::Color, status::GameStatus};
+
+    #[test]
+    fn fools_mate() {
+      let mut board = Board::default();
+      for (from, to) in [(50, 42), (11, 27), (49, 33), (4, 32)] {
+        assert!(board.move_piece(MoveInput::from_id(from, to).unwrap()));
+      }
+      assert!(board.in_check());
+      assert_eq!(board.get_status(), GameStatus::Checkmate { winner: Color::Black });
+    }
+
+    #[test]
+    fn in_check() {
+      let board = Board::from_fen("rnbqkbnr/ppppp1pp/8/5p1Q/4P3/8/PPPP1PPP/RNB1KBNR b KQkq - 1 2");
+      assert!(board.in_check());
+      assert_eq!(board.get_status(), GameStatus::Playing);
+      assert!(!Board::default().in_check());
+    }
+
+    #[test]
+    fn stalemate_with_pinned_piece() {
+      let board = Board::from_fen("5N1k/4N1r1/5Q2/8/8/8/8/K7 b - - 0 1");
+      assert!(!board.in_check());
+      assert_eq!(board.get_status(), GameStatus::Stalemate);
+    }
+
+    #[test]
+    fn insufficient_material() {
+      let board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1");
+      assert_eq!(board.get_status(), GameStatus::InsufficientMaterial);
+    }
+
+    #[test]
+    fn dead_positions() {
+      let dead = [
+        "4k3/8/8/8/8/8/8/4K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/2N1K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/2B1K3 w - - 0 1",
+        "4kb2/8/8/8/8/8/8/2B1K3 w - - 0 1",
+        "4k3/1b1b4/2b5/8/8/5B2/4B3/3BK3 w - - 0 1",
+        "4kb2/8/8/8/8/8/8/4K3 b - - 0 1",
+      ];
+      for fen in dead {
+        let board = Board::from_fen(fen);
+        assert!(board.insufficient_material(), "{fen}");
+        assert_eq!(board.get_status(), GameStatus::InsufficientMaterial, "{fen}");
+      }
+
+      let alive = [
+        "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/1NN1K3 w - - 0 1",
+        "2n1k3/8/8/8/8/8/8/2N1K3 w - - 0 1",
+        "2b1k3/8/8/8/8/8/8/2B1K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/1BB1K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/1NB1K3 w - - 0 1",
+        "2n1k3/8/8/8/8/8/8/2B1K3 w - - 0 1",
+      ];
+      for fen in alive {
+        assert!(!Board::from_fen(fen).insufficient_material(), "{fen}");
+      }
+    }
+
+    #[test]
+    fn insufficient_to_win() {
+      let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1");
+      assert!(!board.insufficient_material_to_win(Color::White));
+      assert!(board.insufficient_material_to_win(Color::Black));
+
+      //a lone knight only mates with help from other enemy pieces
+      let board = Board::from_fen("4k3/4p3/8/8/8/8/8/2N1K3 w - - 0 1");
+      assert!(!board.insufficient_material_to_win(Color::White));
+      assert!(!board.insufficient_material_to_win(Color::Black));
+      let board = Board::from_fen("4k3/8/8/8/8/8/8/2N1K3 w - - 0 1");
+      assert!(board.insufficient_material_to_win(Color::White));
+
+      //bishops need an enemy piece on the other colour to block with
+      let board = Board::from_fen("4kb2/8/8/8/8/8/8/1BB1K3 w - - 0 1");
+      assert!(!board.insufficient_material_to_win(Color::White));
+      let board = Board::from_fen("4kb2/8/8/8/8/8/8/2B1K3 w - - 0 1");
+      assert!(board.insufficient_material_to_win(Color::White));
+      let board = Board::from_fen("2b1k3/8/8/8/8/8/8/2B1K3 w - - 0 1");
+      assert!(!board.insufficient_material_to_win(Color::White));
+      let board = Board::from_fen("2n1k3/8/8/8/8/8/8/2B1K3 w - - 0 1");
+      assert!(!board.insufficient_material_to_win(Color::White));
+    }
+
+    #[test]
+    fn timeout() {
+      let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1");
+      assert_eq!(board.timeout(Color::Black), GameStatus::Timeout { winner: Color::White });
+      assert_eq!(board.timeout(Color::White), GameStatus::InsufficientMaterial);
+    }
+
+    #[test]
+    fn claims_need_a_running_game() {
+      let board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 120 80");
+      assert_eq!(board.claimable_draw(), None);
+
+      let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 120 80");
+      assert_eq!(board.claimable_draw(), Some(GameStatus::FiftyMove));
+      assert_eq!(Board::default().claimable_draw(), None);
+    }
+  }
+
+  mod fen_export {
+    use crate::board::{Board, move_input::MoveInput};
+
+    const FENS: [&str; 8] = [
+      "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+      "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+      "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+      "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+      "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+      "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+      "8/8/1k6/2b5/2pP4/8/5K2/8 b - d3 0 1",
+      "4k3/8/8/8/8/8/8/R3K3 b Qk - 37 42",
+    ];
+
+    fn assert_round_trip(board: &Board) {
+      let fen = board.to_fen();
+      let parsed = Board::from_fen(&fen);
+
+      assert_eq!(parsed.to_fen(), fen);
+      assert_eq!(parsed.white, board.white);
+      assert_eq!(parsed.black, board.black);
+      assert_eq!(parsed.white_turn, board.white_turn);
+      assert_eq!(parsed.clock, board.clock);
+      assert_eq!(parsed.half_clock, board.half_clock);
+      assert_eq!(parsed.en_passant, board.en_passant);
+      assert_eq!(parsed.white_short_castle, board.white_short_castle);
+      assert_eq!(parsed.white_long_castle, board.white_long_castle);
+      assert_eq!(parsed.black_short_castle, board.black_short_castle);
+      assert_eq!(parsed.black_long_castle, board.black_long_castle);
+      assert_eq!(parsed.hash(), board.hash());
+    }
+
+    #[test]
+    fn exact() {
+      for fen in FENS {
+        assert_eq!(Board::from_fen(fen).to_fen(), fen);
+      }
+      assert_eq!(Board::default().to_fen(), FENS[0]);
+    }
+
+    #[test]
+    fn after_moves() {
+      let mut board = Board::default();
+      assert!(board.move_piece(MoveInput::from_id(52, 36).unwrap()));
+      assert_eq!(
+        board.to_fen(),
+        "rnbqkbnr/pppppppp/8/8/3P4/8/PPP1PPPP/RNBQKBNR b KQkq d3 0 1"
+      );
+
+      assert!(board.move_piece(MoveInput::from_id(1, 18).unwrap()));
+      assert_eq!(
+        board.to_fen(),
+        "rnbqkb1r/pppppppp/5n2/8/3P4/8/PPP1PPPP/RNBQKBNR w KQkq - 1 2"
+      );
+    }
+
+    #[test]
+    fn round_trip() {
+      for fen in FENS {
+        let mut board = Board::from_fen(fen);
+        for m in board.legal_moves() {
+          let undo = board.make_move(m.input()).unwrap();
+          assert_round_trip(&board);
+
+          for reply in board.legal_moves() {
+            let undo = board.make_move(reply.input()).unwrap();
+            assert_round_trip(&board);
+            board.unmake_move(undo);
+          }
+          board.unmake_move(undo);
+        }
+      }
+    }
+  }
 }
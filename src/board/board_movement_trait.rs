@@ -1,10 +1,10 @@
 use super::util_fns::mask_from_bool;
 
 pub trait BoardMovement {
-  fn move_left_mask(self, amount: u32) -> u64;
-  fn move_up_mask(self, amount: u32) -> u64;
-  fn move_right_mask(self, amount: u32) -> u64;
-  fn move_down_mask(self, amount: u32) -> u64;
+  fn move_left_mask(self, amount: u32) -> Self;
+  fn move_up_mask(self, amount: u32) -> Self;
+  fn move_right_mask(self, amount: u32) -> Self;
+  fn move_down_mask(self, amount: u32) -> Self;
 }
 
 impl BoardMovement for u64 {
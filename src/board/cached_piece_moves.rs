@@ -1,21 +1,24 @@
-#[derive(Default)]
+use super::bitboard::Bitboard;
+
+#[derive(Clone, Default)]
 pub struct CachedPieceMoves {
-  pub from_mask: u64,
-  pub pawn_default: u64,
-  pub pawn_advance: u64,
-  pub pawn_capture: u64,
-  pub knight: u64,
-  pub bishop: u64,
-  pub rook: u64,
-  pub queen: u64,
-  pub king_default: u64,
-  pub king_short_castle: u64,
-  pub king_long_castle: u64,
-  pub capturing: u64,
+  //the square the moves start from, empty while nothing is cached
+  pub from: Bitboard,
+  pub pawn_default: Bitboard,
+  pub pawn_advance: Bitboard,
+  pub pawn_capture: Bitboard,
+  pub knight: Bitboard,
+  pub bishop: Bitboard,
+  pub rook: Bitboard,
+  pub queen: Bitboard,
+  pub king_default: Bitboard,
+  pub king_short_castle: Bitboard,
+  pub king_long_castle: Bitboard,
+  pub capturing: Bitboard,
 }
 
 impl CachedPieceMoves {
-  pub fn all(&self) -> u64 {
+  pub fn all(&self) -> Bitboard {
     let pawn_moves = self.pawn_default | self.pawn_advance | self.pawn_capture;
     let knight_moves = self.knight;
     let bishop_moves = self.bishop;
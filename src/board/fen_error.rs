@@ -0,0 +1,68 @@
+use std::fmt;
+
+use super::position_error::PositionError;
+
+//every position is the char index of the offending character in the fen string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FenError {
+  FieldCount(usize),
+  Placement(usize),
+  SideToMove(usize),
+  Castling(usize),
+  EnPassant(usize),
+  Halfmove(usize),
+  Fullmove(usize),
+  //the string is well formed but the position is not legal, only reported in strict mode
+  Illegal(Vec<PositionError>),
+}
+
+impl FenError {
+  pub fn position(&self) -> Option<usize> {
+    match *self {
+      FenError::FieldCount(position)
+      | FenError::Placement(position)
+      | FenError::SideToMove(position)
+      | FenError::Castling(position)
+      | FenError::EnPassant(position)
+      | FenError::Halfmove(position)
+      | FenError::Fullmove(position) => Some(position),
+      FenError::Illegal(_) => None,
+    }
+  }
+
+  pub(super) fn map_position(self, map: impl Fn(usize) -> usize) -> FenError {
+    match self {
+      FenError::FieldCount(position) => FenError::FieldCount(map(position)),
+      FenError::Placement(position) => FenError::Placement(map(position)),
+      FenError::SideToMove(position) => FenError::SideToMove(map(position)),
+      FenError::Castling(position) => FenError::Castling(map(position)),
+      FenError::EnPassant(position) => FenError::EnPassant(map(position)),
+      FenError::Halfmove(position) => FenError::Halfmove(map(position)),
+      FenError::Fullmove(position) => FenError::Fullmove(map(position)),
+      FenError::Illegal(errors) => FenError::Illegal(errors),
+    }
+  }
+}
+
+impl fmt::Display for FenError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if let FenError::Illegal(errors) = self {
+      let errors: Vec<String> = errors.iter().map(|error| error.to_string()).collect();
+      return write!(f, "Illegal position: {}", errors.join(", "));
+    }
+
+    let field = match self {
+      FenError::FieldCount(_) => "Expected 6 fields",
+      FenError::Placement(_) => "Invalid piece placement data",
+      FenError::SideToMove(_) => "Invalid active color data",
+      FenError::Castling(_) => "Invalid castling rights data",
+      FenError::EnPassant(_) => "Invalid en passant data",
+      FenError::Halfmove(_) => "Invalid halfmove clock",
+      FenError::Fullmove(_) => "Invalid fullmove number",
+      FenError::Illegal(_) => unreachable!(),
+    };
+    write!(f, "{field} at character {}", self.position().unwrap())
+  }
+}
+
+impl std::error::Error for FenError {}
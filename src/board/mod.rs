@@ -1,10 +1,27 @@
+pub mod attacks;
+pub mod bitboard;
+#[allow(clippy::module_inception)]
 mod board;
-mod board_movement_trait;
-mod cached_piece_moves;
+pub mod board_movement_trait;
+pub mod cached_piece_moves;
+pub mod fen_error;
 mod move_gen;
 pub mod move_input;
+pub mod move_list;
+mod perft;
+pub mod pgn;
+pub mod pgn_error;
+pub mod piece;
+pub mod pieces;
+pub mod position_error;
+mod san;
+pub mod san_error;
+pub mod square;
+pub mod square_error;
 pub mod status;
-mod pieces;
+pub mod undo;
 mod util_fns;
+mod validation;
+mod zobrist;
 
 pub use board::Board;
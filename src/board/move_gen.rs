@@ -1,8 +1,19 @@
 use super::{
-  Board, board_movement_trait::BoardMovement, cached_piece_moves::CachedPieceMoves, status::*,
+  Board,
+  attacks::{
+    between, bishop_attacks, king_attacks, knight_attacks, pawn_attacks, rook_attacks,
+  },
+  board_movement_trait::BoardMovement,
+  cached_piece_moves::CachedPieceMoves,
+  bitboard::Bitboard,
+  move_list::*,
+  piece::{Color, PieceKind},
+  square::Square,
   util_fns::*,
 };
 
+const PROMOTING_MASK: u64 = 0xFF_00_00_00_00_00_00_FF;
+
 pub struct MoveGen {
   saved_white_turn_mask: u64,
   saved_ally: u64,
@@ -33,67 +44,140 @@ impl MoveGen {
     let white_turn_mask = mask_from_bool(board.white_turn);
     let ally = if_mask(
       white_turn_mask,
-      board.white.pieces_concat(),
-      board.black.pieces_concat(),
+      board.white.pieces_concat().0,
+      board.black.pieces_concat().0,
     );
     let enemy = if_mask(
       white_turn_mask,
-      board.black.pieces_concat(),
-      board.white.pieces_concat(),
+      board.black.pieces_concat().0,
+      board.white.pieces_concat().0,
     );
     let empty = !(ally | enemy);
 
-    let movegen = MoveGen {
+    MoveGen {
       saved_white_turn_mask: white_turn_mask,
       saved_ally: ally,
       saved_enemy: enemy,
       saved_empty: empty,
 
-      white_turn_mask: white_turn_mask,
-      ally: ally,
-      enemy: enemy,
-      empty: empty,
-      pawns: board.pawns(),
-      knights: board.knights(),
-      bishops: board.bishops(),
-      rooks: board.rooks(),
-      queens: board.queens(),
-      kings: board.kings(),
-
-      en_passant_mask: board.en_passant_mask,
+      white_turn_mask,
+      ally,
+      enemy,
+      empty,
+      pawns: board.pawns().0,
+      knights: board.knights().0,
+      bishops: board.bishops().0,
+      rooks: board.rooks().0,
+      queens: board.queens().0,
+      kings: board.kings().0,
+
+      en_passant_mask: board.en_passant.0,
       white_short_castle: board.white_short_castle,
       white_long_castle: board.white_long_castle,
       black_short_castle: board.black_short_castle,
-      black_long_castle: board.black_short_castle,
-    };
+      black_long_castle: board.black_long_castle,
+    }
+  }
 
-    movegen
+  pub(super) fn cached(board: &Board, from: Square) -> CachedPieceMoves {
+    let mut movegen = MoveGen::default(board);
+    let check = movegen.check_filter();
+    let danger = movegen.king_danger();
+
+    movegen.piece_moves(from.mask(), check, danger)
+  }
+
+  pub(super) fn legal(board: &Board) -> MoveList {
+    MoveGen::generate(board, false)
   }
 
-  pub(super) fn cached(board: &Board, at_mask: u64) -> CachedPieceMoves {
+  //captures, en passant and promotions, what a quiescence search looks at
+  pub(super) fn captures(board: &Board) -> MoveList {
+    MoveGen::generate(board, true)
+  }
+
+  fn generate(board: &Board, tactical_only: bool) -> MoveList {
     let mut movegen = MoveGen::default(board);
-    let pin = movegen.pin_filter(at_mask);
     let check = movegen.check_filter();
+    let danger = movegen.king_danger();
+
+    let mut moves = MoveList::with_capacity(64);
+    let mut remaining = movegen.ally;
+    while remaining > 0 {
+      let from_mask = remaining & remaining.wrapping_neg();
+      remaining ^= from_mask;
+
+      let cached = movegen.piece_moves(from_mask, check, danger);
+      let tactical = cached.capturing | (cached.pawn_default & Bitboard(PROMOTING_MASK));
+      let targets = if tactical_only { tactical } else { Bitboard::FULL };
+      movegen.push_moves(&cached, targets, &mut moves);
+    }
+
+    moves
+  }
+
+  fn piece_moves(&mut self, at_mask: u64, check: u64, danger: u64) -> CachedPieceMoves {
+    let pin = self.pin_filter(at_mask);
     let filter = pin & check;
 
+    let pawn = at_mask & self.pawns & self.ally;
+    let king = mask_from_bool(at_mask & self.kings & self.ally > 0);
+    let en_passant = mask_from_bool(self.en_passant_legal(pawn)) & self.en_passant_mask;
+    let capture_filter = (filter & !self.en_passant_mask) | en_passant;
+
     let mut moves = CachedPieceMoves {
-      from_mask: at_mask,
-      pawn_default: movegen.pawn_default(at_mask & movegen.pawns & movegen.ally) & filter,
-      pawn_advance: movegen.pawn_advance(at_mask & movegen.pawns & movegen.ally) & filter,
-      pawn_capture: movegen.pawn_capture(at_mask & movegen.pawns & movegen.ally) & filter,
-      knight: movegen.knight(at_mask & movegen.knights & movegen.ally) & filter,
-      bishop: movegen.bishop(at_mask & movegen.bishops & movegen.ally) & filter,
-      rook: movegen.rook(at_mask & movegen.rooks & movegen.ally) & filter,
-      queen: movegen.queen(at_mask & movegen.queens & movegen.ally) & filter,
-      king_default: movegen.king_default(at_mask & movegen.kings & movegen.ally)
-        & !movegen.king_danger(),
-      king_short_castle: movegen.king_short_castle(),
-      king_long_castle: movegen.king_long_castle(),
-      capturing: 0,
+      from: Bitboard(at_mask),
+      pawn_default: Bitboard(self.pawn_default(pawn) & filter),
+      pawn_advance: Bitboard(self.pawn_advance(pawn) & filter),
+      pawn_capture: Bitboard(self.pawn_capture(pawn) & capture_filter),
+      knight: Bitboard(self.knight(at_mask & self.knights & self.ally) & filter),
+      bishop: Bitboard(self.bishop(at_mask & self.bishops & self.ally) & filter),
+      rook: Bitboard(self.rook(at_mask & self.rooks & self.ally) & filter),
+      queen: Bitboard(self.queen(at_mask & self.queens & self.ally) & filter),
+      king_default: Bitboard(self.king_default(at_mask & self.kings & self.ally) & !danger),
+      king_short_castle: Bitboard(king & self.king_short_castle()),
+      king_long_castle: Bitboard(king & self.king_long_castle()),
+      capturing: Bitboard::EMPTY,
     };
-    moves.capturing = moves.all() & movegen.enemy;
+    moves.capturing = moves.all() & Bitboard(self.enemy | en_passant);
     moves
   }
+
+  fn push_moves(&self, cached: &CachedPieceMoves, targets: Bitboard, moves: &mut MoveList) {
+    let pawn_moves = cached.pawn_default | cached.pawn_capture;
+    let castles = cached.king_short_castle | cached.king_long_castle;
+    let en_passant = cached.pawn_capture & Bitboard(self.en_passant_mask);
+
+    let Some(from) = cached.from.first() else {
+      return;
+    };
+
+    for to in cached.all() & targets {
+      let mut flags = QUIET;
+      flags |= if_bool(cached.capturing.contains(to), CAPTURE, 0);
+      flags |= if_bool(en_passant.contains(to), EN_PASSANT, 0);
+      flags |= if_bool(castles.contains(to), CASTLE, 0);
+      flags |= if_bool(cached.pawn_advance.contains(to), DOUBLE_PUSH, 0);
+
+      if (pawn_moves & Bitboard(PROMOTING_MASK)).contains(to) {
+        for promotion in PieceKind::PROMOTIONS {
+          moves.push(Move {
+            from,
+            to,
+            promotion: Some(promotion),
+            flags,
+          });
+        }
+      } else {
+        moves.push(Move {
+          from,
+          to,
+          promotion: None,
+          flags,
+        });
+      }
+    }
+  }
 }
 
 //move gen
@@ -132,59 +216,19 @@ impl MoveGen {
   }
 
   fn pawn_capture(&self, at_mask: u64) -> u64 {
-    let one_move_up = at_mask.move_up_mask(1);
-    let one_move_down = at_mask.move_down_mask(1);
-
-    let enemy_to_left = if_mask(
-      self.white_turn_mask,
-      one_move_up.move_left_mask(1),
-      one_move_down.move_left_mask(1),
-    );
-    let enemy_to_right = if_mask(
-      self.white_turn_mask,
-      one_move_up.move_right_mask(1),
-      one_move_down.move_right_mask(1),
-    );
-
-    (enemy_to_left | enemy_to_right) & (self.enemy | self.en_passant_mask)
+    pawn_attacks(self.color(), at_mask) & (self.enemy | self.en_passant_mask)
   }
 
   fn knight(&self, at_mask: u64) -> u64 {
-    let offsets = [
-      (-1, -2),
-      (1, -2),
-      (-2, -1),
-      (2, -1),
-      (-2, 1),
-      (2, 1),
-      (-1, 2),
-      (1, 2),
-    ]
-    .to_vec();
-
-    self.offset(at_mask, offsets)
+    knight_attacks(at_mask) & (self.empty | self.enemy)
   }
 
   fn bishop(&self, at_mask: u64) -> u64 {
-    let mut moves = 0;
-
-    moves |= self.iterative(at_mask, -1, -1);
-    moves |= self.iterative(at_mask, 1, -1);
-    moves |= self.iterative(at_mask, -1, 1);
-    moves |= self.iterative(at_mask, 1, 1);
-
-    moves
+    self.slide(at_mask, bishop_attacks)
   }
 
   fn rook(&self, at_mask: u64) -> u64 {
-    let mut moves = 0;
-
-    moves |= self.iterative(at_mask, -1, 0);
-    moves |= self.iterative(at_mask, 0, -1);
-    moves |= self.iterative(at_mask, 1, 0);
-    moves |= self.iterative(at_mask, 0, 1);
-
-    moves
+    self.slide(at_mask, rook_attacks)
   }
 
   fn queen(&self, at_mask: u64) -> u64 {
@@ -192,73 +236,16 @@ impl MoveGen {
   }
 
   fn king_default(&self, at_mask: u64) -> u64 {
-    let offsets = [
-      (-1, -1),
-      (0, -1),
-      (1, -1),
-      (-1, 0),
-      (1, 0),
-      (-1, 1),
-      (0, 1),
-      (1, 1),
-    ]
-    .to_vec();
-
-    self.offset(at_mask, offsets)
+    king_attacks(at_mask) & (self.empty | self.enemy)
   }
 
-  fn offset(&self, at_mask: u64, offsets: Vec<(i32, i32)>) -> u64 {
-    let mut moves = 0;
-
-    for (dx, dy) in offsets {
-      let mut new_move = if_bool(
-        dx > 0,
-        at_mask.move_right_mask(dx.unsigned_abs()),
-        at_mask.move_left_mask(dx.unsigned_abs()),
-      );
-
-      new_move = if_bool(
-        dy > 0,
-        new_move.move_up_mask(dy.unsigned_abs()),
-        new_move.move_down_mask(dy.unsigned_abs()),
-      );
-
-      moves |= new_move & (self.empty | self.enemy);
-    }
-
-    moves
-  }
-
-  fn iterative(&self, at_mask: u64, dx: i32, dy: i32) -> u64 {
-    let mut moves = 0;
-
-    let pos_dx = mask_from_bool(dx > 0);
-    let pos_dy = mask_from_bool(dy > 0);
-
-    for i in 1..=7 {
-      let mut new_move = if_mask(
-        pos_dx,
-        at_mask.move_right_mask(i * dx.unsigned_abs()),
-        at_mask.move_left_mask(i * dx.unsigned_abs()),
-      );
-      new_move = if_mask(
-        pos_dy,
-        new_move.move_up_mask(i * dy.unsigned_abs()),
-        new_move.move_down_mask(i * dy.unsigned_abs()),
-      );
-
-      let within_board = new_move > 0;
-      let is_enemy = new_move & self.enemy > 0;
-      let is_empty = new_move & self.empty > 0;
-
-      moves |= new_move & mask_from_bool(is_enemy || is_empty);
-
-      if !within_board || !is_empty {
-        break;
-      }
-    }
-
-    moves
+  //every piece in the mask slides until the first occupied square, which it may capture
+  fn slide(&self, at_mask: u64, attacks: fn(usize, u64) -> u64) -> u64 {
+    let occupied = !self.empty;
+    Bitboard(at_mask)
+      .into_iter()
+      .fold(0, |moves, square| moves | attacks(square.index(), occupied))
+      & (self.enemy | self.empty)
   }
 
   fn king_long_castle(&mut self) -> u64 {
@@ -342,34 +329,22 @@ impl MoveGen {
 
 //other
 impl MoveGen {
-  fn pin_filter(&mut self, at_mask: u64) -> u64 {
+  fn pin_filter(&self, at_mask: u64) -> u64 {
     let diag = (self.bishops | self.queens) & self.enemy;
     let not_diag = (self.rooks | self.queens) & self.enemy;
-
-    let directions = [
-      (-1, 1, diag),
-      (0, 1, not_diag),
-      (1, 1, diag),
-      (-1, 0, not_diag),
-      (1, 0, not_diag),
-      (-1, -1, diag),
-      (0, -1, not_diag),
-      (1, -1, diag),
-    ];
+    //the piece is pinned if a slider sees the king once it has stepped aside
+    let occupied = !self.empty & !at_mask;
 
     let mut pin_path = 0;
-    let ally_king = self.kings & self.ally;
-
-    self.save();
-    for (dx, dy, diag) in directions {
-      self.remove_piece(at_mask);
-      let king_to_pinner_path = self.iterative(ally_king, dx, dy);
-      let king_hits_pinner = king_to_pinner_path & diag > 0;
-      self.load();
-      let path_contains_pin = king_to_pinner_path & at_mask > 0;
-
-      let pinned = mask_from_bool(king_hits_pinner & path_contains_pin);
-      pin_path |= pinned & king_to_pinner_path & !at_mask;
+    for king in Bitboard(self.kings & self.ally) {
+      let pinners = (bishop_attacks(king.index(), occupied) & diag)
+        | (rook_attacks(king.index(), occupied) & not_diag);
+
+      for pinner in Bitboard(pinners) {
+        let king_to_pinner_path = between(king.index(), pinner.index()) | pinner.mask();
+        let pinned = mask_from_bool(king_to_pinner_path & at_mask > 0);
+        pin_path |= pinned & king_to_pinner_path & !at_mask;
+      }
     }
 
     mask_from_bool(pin_path == 0) | pin_path
@@ -380,31 +355,24 @@ impl MoveGen {
     let mut filter = 0;
     filter |= self.pawn_capture(ally_king) & (self.pawns & self.enemy);
     filter |= self.knight(ally_king) & (self.knights & self.enemy);
-
-    let directions = [
-      (-1, 1, u64::MAX),
-      (0, 1, 0),
-      (1, 1, u64::MAX),
-      (-1, 0, 0),
-      (1, 0, 0),
-      (-1, -1, u64::MAX),
-      (0, -1, 0),
-      (1, -1, u64::MAX),
-    ];
+    let mut checkers = filter.count_ones();
 
     let diag = self.enemy & (self.bishops | self.queens);
     let not_diag = self.enemy & (self.rooks | self.queens);
 
-    for (dx, dy, diag_mask) in directions {
-      let attacker = if_mask(diag_mask, diag, not_diag);
-
-      let king_to_attacker_path = self.iterative(ally_king, dx, dy);
-      let king_hits_attacker = mask_from_bool(king_to_attacker_path & attacker > 0);
+    for king in Bitboard(ally_king) {
+      let attackers = (bishop_attacks(king.index(), !self.empty) & diag)
+        | (rook_attacks(king.index(), !self.empty) & not_diag);
 
-      filter |= king_hits_attacker & king_to_attacker_path;
+      for attacker in Bitboard(attackers) {
+        filter |= between(king.index(), attacker.index()) | attacker.mask();
+        checkers += 1;
+      }
     }
 
-    if_bool(filter > 0, filter, u64::MAX)
+    //in double check only the king can move
+    let double_check = mask_from_bool(checkers > 1);
+    !double_check & if_bool(filter > 0, filter, u64::MAX)
   }
 
   fn king_danger(&mut self) -> u64 {
@@ -412,68 +380,53 @@ impl MoveGen {
     self.switch_turn();
     self.save();
 
-    self.empty_is_enemy();
-    king_danger |= self.pawn_capture(self.pawns & self.ally);
-    king_danger |= self.knight(self.knights & self.ally);
-    king_danger |= self.king_default(self.kings & self.ally);
-
-    self.load();
+    //the king must not be able to hide behind itself or capture a defended piece
     self.remove_piece(self.kings & self.enemy);
+    self.enemy |= self.ally;
     king_danger |= self.bishop(self.bishops & self.ally);
     king_danger |= self.rook(self.rooks & self.ally);
     king_danger |= self.queen(self.queens & self.ally);
 
+    self.empty_is_enemy();
+    king_danger |= self.pawn_capture(self.pawns & self.ally);
+    king_danger |= self.knight(self.knights & self.ally);
+    king_danger |= self.king_default(self.kings & self.ally);
+
     self.load();
     self.switch_turn();
     king_danger
   }
 
-  pub fn get_status(&mut self) -> u64 {
-    let filter = self.check_filter();
-    let checked = self.king_danger() & (self.kings & self.ally) > 0;
-    let danger = self.king_danger();
-    let mut moves = self.pawn_default(self.pawns & self.ally) 
-      | self.pawn_advance(self.pawns & self.ally) 
-      | self.pawn_capture(self.pawns & self.ally) 
-      | self.knight(self.knights & self.ally) 
-      | self.bishop((self.queens | self.bishops) & self.ally) 
-      | self.rook((self.queens | self.rooks) & self.ally) 
-      | self.king_short_castle() 
-      | self.king_long_castle();
-
-    moves = moves & filter | (self.king_default(self.kings & self.ally) & !danger);
-
-    let no_moves = moves == 0;
-    let winner = if_mask(self.white_turn_mask, BLACK_WON, WHITE_WON);
-
-    let checkmate = mask_from_bool(checked && no_moves);
-    let stalemate = mask_from_bool(!checked && no_moves);
-
-    let king_vs_king = self.ally | self.enemy == self.kings;
-
-    let knight_count = self.knights.count_ones();
-    let ally_bishop_count = (self.ally & self.bishops).count_ones();
-    let enemy_bishop_count = (self.enemy & self.bishops).count_ones();
-
-    let king_minor_vs_king = (self.ally | self.enemy) == (self.kings | self.knights | self.bishops)
-      && (knight_count + ally_bishop_count + enemy_bishop_count == 1);
-
-    let black_squares = 0xAA_55_AA_55_AA_55_AA_55_u64;
-    let same_square_color = (self.bishops & black_squares).count_ones() > 0;
+  pub(super) fn in_check(&mut self) -> bool {
+    self.king_danger() & self.kings & self.ally > 0
+  }
 
-    let king_bishop_vs_king_bishop = (self.ally | self.enemy) == (self.kings | self.bishops)
-      && (ally_bishop_count == 1 && enemy_bishop_count == 1)
-      && same_square_color;
+  fn en_passant_legal(&mut self, at_mask: u64) -> bool {
+    let target = self.pawn_capture(at_mask) & self.en_passant_mask & self.empty;
+    let captured = if_mask(
+      self.white_turn_mask,
+      target.move_down_mask(1),
+      target.move_up_mask(1),
+    );
 
-    let insufficient_material =
-      mask_from_bool(king_vs_king || king_minor_vs_king | king_bishop_vs_king_bishop);
+    //both pawns leave their squares, so pins alone can't tell if the king is exposed
+    self.save();
+    self.remove_piece(at_mask | captured);
+    self.ally |= target;
+    self.empty &= !target;
+    let safe = self.check_filter() == u64::MAX;
+    self.load();
 
-    (checkmate & winner) | ((insufficient_material | stalemate) & DRAW)
+    target > 0 && safe
   }
 }
 
 //checkpoints / modifiers
 impl MoveGen {
+  fn color(&self) -> Color {
+    if self.white_turn_mask > 0 { Color::White } else { Color::Black }
+  }
+
   fn switch_turn(&mut self) {
     self.white_turn_mask = !self.white_turn_mask;
     std::mem::swap(&mut self.ally, &mut self.enemy);
@@ -511,7 +464,9 @@ mod tests {
     use crate::board::{
       Board,
       move_gen::MoveGen,
-      move_input::{BISHOP, KNIGHT, MoveInput, NONE, QUEEN, ROOK},
+      move_input::MoveInput,
+      piece::PieceKind,
+      square::Square,
     };
 
     #[test]
@@ -597,7 +552,7 @@ mod tests {
     #[test]
     fn en_passant() {
       let mut board = Board::from_fen("k7/p7/8/1P6/1p6/8/P7/K7 w - - 0 1");
-      board.move_piece(MoveInput::from_id(55, 39));
+      board.move_piece(MoveInput::from_id(55, 39).unwrap());
       let movegen = MoveGen::default(&board);
       assert_eq!(
         movegen.pawn_capture(0x00_00_00_40_00_00_00_00),
@@ -605,7 +560,7 @@ mod tests {
       );
 
       let mut board = Board::from_fen("k7/p7/8/1P6/1p6/8/P7/K7 b - - 0 1");
-      board.move_piece(MoveInput::from_id(15, 31));
+      board.move_piece(MoveInput::from_id(15, 31).unwrap());
       let movegen = MoveGen::default(&board);
       assert_eq!(
         movegen.pawn_capture(0x00_00_00_00_40_00_00_00),
@@ -628,17 +583,23 @@ mod tests {
         0x80_00_00_00_00_00_00_00
       );
 
-      board.move_piece(MoveInput::with_promotion(
-        0x00_00_00_00_00_00_80_00,
-        0x00_00_00_00_00_00_00_80,
-        KNIGHT,
-      ));
+      board.move_piece(
+        MoveInput::with_promotion(
+          Square::from_mask(0x00_00_00_00_00_00_80_00).unwrap(),
+          Square::from_mask(0x00_00_00_00_00_00_00_80).unwrap(),
+          PieceKind::Knight,
+        )
+        .unwrap(),
+      );
 
-      board.move_piece(MoveInput::with_promotion(
-        0x00_80_00_00_00_00_00_00,
-        0x80_00_00_00_00_00_00_00,
-        KNIGHT,
-      ));
+      board.move_piece(
+        MoveInput::with_promotion(
+          Square::from_mask(0x00_80_00_00_00_00_00_00).unwrap(),
+          Square::from_mask(0x80_00_00_00_00_00_00_00).unwrap(),
+          PieceKind::Knight,
+        )
+        .unwrap(),
+      );
 
       let movegen = MoveGen::default(&board);
       assert_eq!(movegen.pawns, 0);
@@ -661,17 +622,23 @@ mod tests {
         0x80_00_00_00_00_00_00_00
       );
 
-      board.move_piece(MoveInput::with_promotion(
-        0x00_00_00_00_00_00_80_00,
-        0x00_00_00_00_00_00_00_80,
-        BISHOP,
-      ));
+      board.move_piece(
+        MoveInput::with_promotion(
+          Square::from_mask(0x00_00_00_00_00_00_80_00).unwrap(),
+          Square::from_mask(0x00_00_00_00_00_00_00_80).unwrap(),
+          PieceKind::Bishop,
+        )
+        .unwrap(),
+      );
 
-      board.move_piece(MoveInput::with_promotion(
-        0x00_80_00_00_00_00_00_00,
-        0x80_00_00_00_00_00_00_00,
-        BISHOP,
-      ));
+      board.move_piece(
+        MoveInput::with_promotion(
+          Square::from_mask(0x00_80_00_00_00_00_00_00).unwrap(),
+          Square::from_mask(0x80_00_00_00_00_00_00_00).unwrap(),
+          PieceKind::Bishop,
+        )
+        .unwrap(),
+      );
 
       let movegen = MoveGen::default(&board);
       assert_eq!(movegen.pawns, 0);
@@ -694,17 +661,23 @@ mod tests {
         0x80_00_00_00_00_00_00_00
       );
 
-      board.move_piece(MoveInput::with_promotion(
-        0x00_00_00_00_00_00_80_00,
-        0x00_00_00_00_00_00_00_80,
-        ROOK,
-      ));
+      board.move_piece(
+        MoveInput::with_promotion(
+          Square::from_mask(0x00_00_00_00_00_00_80_00).unwrap(),
+          Square::from_mask(0x00_00_00_00_00_00_00_80).unwrap(),
+          PieceKind::Rook,
+        )
+        .unwrap(),
+      );
 
-      board.move_piece(MoveInput::with_promotion(
-        0x00_80_00_00_00_00_00_00,
-        0x80_00_00_00_00_00_00_00,
-        ROOK,
-      ));
+      board.move_piece(
+        MoveInput::with_promotion(
+          Square::from_mask(0x00_80_00_00_00_00_00_00).unwrap(),
+          Square::from_mask(0x80_00_00_00_00_00_00_00).unwrap(),
+          PieceKind::Rook,
+        )
+        .unwrap(),
+      );
 
       let movegen = MoveGen::default(&board);
       assert_eq!(movegen.pawns, 0);
@@ -727,17 +700,23 @@ mod tests {
         0x80_00_00_00_00_00_00_00
       );
 
-      board.move_piece(MoveInput::with_promotion(
-        0x00_00_00_00_00_00_80_00,
-        0x00_00_00_00_00_00_00_80,
-        QUEEN,
-      ));
+      board.move_piece(
+        MoveInput::with_promotion(
+          Square::from_mask(0x00_00_00_00_00_00_80_00).unwrap(),
+          Square::from_mask(0x00_00_00_00_00_00_00_80).unwrap(),
+          PieceKind::Queen,
+        )
+        .unwrap(),
+      );
 
-      board.move_piece(MoveInput::with_promotion(
-        0x00_80_00_00_00_00_00_00,
-        0x80_00_00_00_00_00_00_00,
-        QUEEN,
-      ));
+      board.move_piece(
+        MoveInput::with_promotion(
+          Square::from_mask(0x00_80_00_00_00_00_00_00).unwrap(),
+          Square::from_mask(0x80_00_00_00_00_00_00_00).unwrap(),
+          PieceKind::Queen,
+        )
+        .unwrap(),
+      );
 
       let movegen = MoveGen::default(&board);
       assert_eq!(movegen.pawns, 0);
@@ -753,11 +732,11 @@ mod tests {
         movegen.pawn_default(0x00_00_00_00_00_00_80_00),
         0x00_00_00_00_00_00_00_80
       );
-      board.move_piece(MoveInput::with_promotion(
-        0x00_00_00_00_00_00_80_00,
-        0x00_00_00_00_00_00_00_80,
-        NONE,
-      ));
+      board.move_piece(MoveInput {
+        from: Square::from_mask(0x00_00_00_00_00_00_80_00).unwrap(),
+        to: Square::from_mask(0x00_00_00_00_00_00_00_80).unwrap(),
+        promotion: None,
+      });
       let movegen = MoveGen::default(&board);
       assert_eq!(movegen.pawns & movegen.ally, 0x00_00_00_00_00_00_80_00);
       assert_eq!(movegen.knights, 0);
@@ -771,11 +750,11 @@ mod tests {
         movegen.pawn_default(0x00_80_00_00_00_00_00_00),
         0x80_00_00_00_00_00_00_00
       );
-      board.move_piece(MoveInput::with_promotion(
-        0x00_80_00_00_00_00_00_00,
-        0x80_00_00_00_00_00_00_00,
-        NONE,
-      ));
+      board.move_piece(MoveInput {
+        from: Square::from_mask(0x00_80_00_00_00_00_00_00).unwrap(),
+        to: Square::from_mask(0x80_00_00_00_00_00_00_00).unwrap(),
+        promotion: None,
+      });
       let movegen = MoveGen::default(&board);
       assert_eq!(movegen.pawns & movegen.ally, 0x00_80_00_00_00_00_00_00);
       assert_eq!(movegen.knights, 0);
@@ -1,43 +1,127 @@
-use super::util_fns::mask_from_bool;
-
-pub const NONE: u64 = 0;
-pub const KNIGHT: u64 = 1;
-pub const BISHOP: u64 = 2;
-pub const ROOK: u64 = 4;
-pub const QUEEN: u64 = 8;
+use super::{piece::PieceKind, square::Square};
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct MoveInput {
-  pub from: u64,
-  pub to: u64,
-  pub promotion: u64,
+  pub from: Square,
+  pub to: Square,
+  pub promotion: Option<PieceKind>,
 }
 
 impl MoveInput {
-  pub fn default(from: u64, to: u64) -> MoveInput {
+  pub fn new(from: Square, to: Square) -> MoveInput {
     MoveInput {
-      from: from,
-      to: to,
-      promotion: 0,
+      from,
+      to,
+      promotion: None,
     }
   }
 
-  pub fn from_id(from: usize, to: usize) -> MoveInput {
-    let valid = mask_from_bool(from < 64 && to < 64 && from != to);
-    MoveInput {
-      from: valid & 1 << from,
-      to: valid & 1 << to,
-      promotion: 0,
+  pub fn from_id(from: usize, to: usize) -> Option<MoveInput> {
+    let from = Square::from_index(from)?;
+    let to = Square::from_index(to)?;
+    (from != to).then(|| MoveInput::new(from, to))
+  }
+
+  //only knights, bishops, rooks and queens are promotions
+  pub fn with_promotion(from: Square, to: Square, promotion: PieceKind) -> Option<MoveInput> {
+    promotion.is_promotion().then_some(MoveInput {
+      from,
+      to,
+      promotion: Some(promotion),
+    })
+  }
+
+  //long algebraic coordinates as used by uci, e.g. e2e4 or e7e8q
+  pub fn from_uci(uci: &str) -> Option<MoveInput> {
+    let from = uci.get(0..2)?.parse::<Square>().ok()?;
+    let to = uci.get(2..4)?.parse::<Square>().ok()?;
+    if from == to {
+      return None;
+    }
+
+    let mut promotion = uci[4..].chars();
+    match (promotion.next(), promotion.next()) {
+      (None, _) => Some(MoveInput::new(from, to)),
+      (Some(letter), None) if letter.is_ascii_lowercase() => {
+        MoveInput::with_promotion(from, to, PieceKind::from_letter(letter)?)
+      }
+      _ => None,
     }
   }
 
-  pub fn with_promotion(from: u64, to: u64, promotion: u64) -> MoveInput {
-    let valid = mask_from_bool(
-      promotion == KNIGHT || promotion == BISHOP || promotion == ROOK || promotion == QUEEN,
-    );
-    MoveInput {
-      from: valid & from,
-      to: valid & to,
-      promotion: valid & promotion,
+  pub fn to_uci(&self) -> String {
+    match self.promotion {
+      Some(kind) => format!("{}{}{}", self.from, self.to, kind.letter().to_ascii_lowercase()),
+      None => format!("{}{}", self.from, self.to),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  mod uci {
+    use crate::board::{move_input::MoveInput, piece::PieceKind, square::Square};
+
+    fn square(name: &str) -> Square {
+      name.parse().unwrap()
+    }
+
+    #[test]
+    fn parse() {
+      let input = MoveInput::from_uci("e2e4").unwrap();
+      assert_eq!(input, MoveInput::new(square("e2"), square("e4")));
+      assert_eq!((input.from.index(), input.to.index()), (51, 35));
+
+      let input = MoveInput::from_uci("e7e8q").unwrap();
+      assert_eq!((input.from, input.to), (square("e7"), square("e8")));
+      assert_eq!(input.promotion, Some(PieceKind::Queen));
+
+      let input = MoveInput::from_uci("a2a1n").unwrap();
+      assert_eq!(input.promotion, Some(PieceKind::Knight));
+    }
+
+    #[test]
+    fn invalid() {
+      let invalid = [
+        "", "e2", "e2e", "e2e9", "e2e2", "e7e8k", "e7e8p", "e7e8Q", "e7e8qq", "e2-e4", "é2e4",
+      ];
+      for uci in invalid {
+        assert!(MoveInput::from_uci(uci).is_none(), "{uci}");
+      }
+    }
+
+    #[test]
+    fn format() {
+      assert_eq!(MoveInput::from_id(51, 35).unwrap().to_uci(), "e2e4");
+      let promotion = MoveInput::with_promotion(square("e7"), square("e8"), PieceKind::Queen);
+      assert_eq!(promotion.unwrap().to_uci(), "e7e8q");
+    }
+
+    #[test]
+    fn invalid_construction() {
+      assert_eq!(MoveInput::from_id(64, 3), None);
+      assert_eq!(MoveInput::from_id(3, 3), None);
+      assert_eq!(MoveInput::with_promotion(square("e7"), square("e8"), PieceKind::King), None);
+    }
+
+    #[test]
+    fn missing_promotion() {
+      let mut board = crate::board::Board::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1");
+      assert!(!board.move_piece(MoveInput::from_uci("a7a8").unwrap()));
+      assert_eq!(board.to_fen(), "4k3/P7/8/8/8/8/8/4K3 w - - 0 1");
+      assert!(board.move_piece(MoveInput::from_uci("a7a8r").unwrap()));
+      assert_eq!(board.to_fen(), "R3k3/8/8/8/8/8/8/4K3 b - - 0 1");
+    }
+
+    #[test]
+    fn round_trip() {
+      let board = crate::board::Board::from_fen(
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+      );
+      for m in board.legal_moves() {
+        let parsed = MoveInput::from_uci(&m.input().to_uci()).unwrap();
+        assert_eq!(parsed, m.input());
+      }
     }
   }
 }
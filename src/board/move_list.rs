@@ -0,0 +1,43 @@
+use super::{move_input::MoveInput, piece::PieceKind, square::Square};
+
+pub const QUIET: u64 = 0;
+pub const CAPTURE: u64 = 1;
+pub const EN_PASSANT: u64 = 2;
+pub const CASTLE: u64 = 4;
+pub const DOUBLE_PUSH: u64 = 8;
+
+pub type MoveList = Vec<Move>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Move {
+  pub from: Square,
+  pub to: Square,
+  pub promotion: Option<PieceKind>,
+  pub flags: u64,
+}
+
+impl Move {
+  pub fn input(&self) -> MoveInput {
+    MoveInput {
+      from: self.from,
+      to: self.to,
+      promotion: self.promotion,
+    }
+  }
+
+  pub fn is_capture(&self) -> bool {
+    self.flags & CAPTURE > 0
+  }
+
+  pub fn is_en_passant(&self) -> bool {
+    self.flags & EN_PASSANT > 0
+  }
+
+  pub fn is_castle(&self) -> bool {
+    self.flags & CASTLE > 0
+  }
+
+  pub fn is_double_push(&self) -> bool {
+    self.flags & DOUBLE_PUSH > 0
+  }
+}
@@ -0,0 +1,188 @@
+use super::{Board, move_list::Move};
+
+impl Board {
+  pub fn perft(&mut self, depth: u32) -> u64 {
+    if depth == 0 {
+      return 1;
+    }
+
+    let moves = self.legal_moves();
+    if depth == 1 {
+      return moves.len() as u64;
+    }
+
+    let mut nodes = 0;
+    for m in moves {
+      let undo = self.make_move(m.input()).unwrap();
+      nodes += self.perft(depth - 1);
+      self.unmake_move(undo);
+    }
+    nodes
+  }
+
+  pub fn divide(&mut self, depth: u32) -> Vec<(Move, u64)> {
+    if depth == 0 {
+      return Vec::new();
+    }
+
+    let mut divide = Vec::new();
+    for m in self.legal_moves() {
+      let undo = self.make_move(m.input()).unwrap();
+      divide.push((m, self.perft(depth - 1)));
+      self.unmake_move(undo);
+    }
+    divide
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::board::Board;
+
+  fn assert_perft(fen: &str, expected: &[u64]) {
+    let mut board = Board::from_fen(fen);
+    for (depth, &nodes) in expected.iter().enumerate() {
+      assert_eq!(board.perft(depth as u32 + 1), nodes, "depth {}", depth + 1);
+    }
+  }
+
+  #[test]
+  fn start_position() {
+    assert_perft(
+      "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+      &[20, 400, 8902, 197281],
+    );
+  }
+
+  #[test]
+  fn kiwipete() {
+    assert_perft(
+      "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+      &[48, 2039, 97862],
+    );
+  }
+
+  #[test]
+  fn position_3() {
+    assert_perft(
+      "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+      &[14, 191, 2812, 43238],
+    );
+  }
+
+  #[test]
+  fn position_4() {
+    assert_perft(
+      "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+      &[6, 264, 9467],
+    );
+  }
+
+  #[test]
+  fn position_4_mirrored() {
+    assert_perft(
+      "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1",
+      &[6, 264, 9467],
+    );
+  }
+
+  #[test]
+  fn position_5() {
+    assert_perft(
+      "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+      &[44, 1486, 62379],
+    );
+  }
+
+  #[test]
+  fn position_6() {
+    assert_perft(
+      "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+      &[46, 2079, 89890],
+    );
+  }
+
+  #[test]
+  fn castling_rights_lost_on_rook_capture() {
+    assert_perft("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", &[26, 568, 13744]);
+  }
+
+  #[test]
+  fn illegal_en_passant_discovered_check() {
+    assert_eq!(Board::from_fen("3k4/3p4/8/K1P4r/8/8/8/8 b - - 0 1").perft(6), 1134888);
+  }
+
+  #[test]
+  fn illegal_en_passant_diagonal_pin() {
+    assert_eq!(Board::from_fen("8/8/4k3/8/2p5/8/B2P2K1/8 w - - 0 1").perft(6), 1015133);
+  }
+
+  #[test]
+  fn en_passant_gives_check() {
+    assert_eq!(Board::from_fen("8/8/1k6/2b5/2pP4/8/5K2/8 b - d3 0 1").perft(6), 1440467);
+  }
+
+  #[test]
+  fn short_castle_gives_check() {
+    assert_eq!(Board::from_fen("5k2/8/8/8/8/8/8/4K2R w K - 0 1").perft(6), 661072);
+  }
+
+  #[test]
+  fn long_castle_gives_check() {
+    assert_eq!(Board::from_fen("3k4/8/8/8/8/8/8/R3K3 w Q - 0 1").perft(6), 803711);
+  }
+
+  #[test]
+  fn castling_rights() {
+    assert_eq!(Board::from_fen("r3k2r/1b4bq/8/8/8/8/7B/R3K2R w KQkq - 0 1").perft(4), 1274206);
+  }
+
+  #[test]
+  fn castling_prevented() {
+    assert_eq!(Board::from_fen("r3k2r/8/3Q4/8/8/5q2/8/R3K2R b KQkq - 0 1").perft(4), 1720476);
+  }
+
+  #[test]
+  fn promotion_out_of_check() {
+    assert_eq!(Board::from_fen("2K2r2/4P3/8/8/8/8/8/3k4 w - - 0 1").perft(6), 3821001);
+  }
+
+  #[test]
+  fn discovered_check() {
+    assert_eq!(Board::from_fen("8/8/1P2K3/8/2n5/1q6/8/5k2 b - - 0 1").perft(5), 1004658);
+  }
+
+  #[test]
+  fn promotion_gives_check() {
+    assert_eq!(Board::from_fen("4k3/1P6/8/8/8/8/K7/8 w - - 0 1").perft(6), 217342);
+  }
+
+  #[test]
+  fn underpromotion_gives_check() {
+    assert_eq!(Board::from_fen("8/P1k5/K7/8/8/8/8/8 w - - 0 1").perft(6), 92683);
+  }
+
+  #[test]
+  fn self_stalemate() {
+    assert_eq!(Board::from_fen("K1k5/8/P7/8/8/8/8/8 w - - 0 1").perft(6), 2217);
+  }
+
+  #[test]
+  fn stalemate_and_checkmate() {
+    assert_eq!(Board::from_fen("8/k1P5/8/1K6/8/8/8/8 w - - 0 1").perft(7), 567584);
+  }
+
+  #[test]
+  fn stalemate_and_checkmate_2() {
+    assert_eq!(Board::from_fen("8/8/2k5/5q2/5n2/8/5K2/8 b - - 0 1").perft(4), 23527);
+  }
+
+  #[test]
+  fn divide_sums_to_perft() {
+    let mut board = Board::default();
+    let divide = board.divide(3);
+
+    assert_eq!(divide.len(), 20);
+    assert_eq!(divide.iter().map(|(_, nodes)| nodes).sum::<u64>(), 8902);
+  }
+}
@@ -0,0 +1,784 @@
+use std::iter::Peekable;
+
+use super::{
+  Board,
+  move_input::MoveInput,
+  pgn_error::PgnError,
+  san_error::SanError,
+  status::GameStatus,
+};
+
+const RESULTS: [&str; 4] = ["1-0", "0-1", "1/2-1/2", "*"];
+const SEVEN_TAG_ROSTER: [(&str, &str); 7] = [
+  ("Event", "?"),
+  ("Site", "?"),
+  ("Date", "????.??.??"),
+  ("Round", "?"),
+  ("White", "?"),
+  ("Black", "?"),
+  ("Result", "*"),
+];
+const LINE_WIDTH: usize = 80;
+
+#[derive(Clone)]
+pub struct PgnGame {
+  pub tags: Vec<(String, String)>,
+  //only legal moves get in through push, so every one of them has a san
+  moves: Vec<MoveInput>,
+  pub result: Option<String>,
+  //endings the board can't detect, like resignation or timeout
+  pub status: Option<GameStatus>,
+  pub start: Board,
+  //position after the last move of the mainline
+  pub board: Board,
+}
+
+impl Default for PgnGame {
+  fn default() -> PgnGame {
+    PgnGame::new(Board::default())
+  }
+}
+
+impl PgnGame {
+  //constructor
+  pub fn new(start: Board) -> PgnGame {
+    PgnGame {
+      tags: Vec::new(),
+      moves: Vec::new(),
+      result: None,
+      status: None,
+      board: start.clone(),
+      start,
+    }
+  }
+
+  //recording
+  //the move has to be in the legal move list, promotion included
+  pub fn push(&mut self, input: MoveInput) -> bool {
+    let legal = self.board.legal_moves().iter().any(|m| m.input() == input);
+    if legal && self.board.move_piece(input) {
+      self.moves.push(input);
+      return true;
+    }
+    false
+  }
+
+  pub fn finish(&mut self, status: GameStatus) {
+    self.status = Some(status);
+  }
+
+  pub fn set_tag(&mut self, name: &str, value: &str) {
+    match self.tags.iter_mut().find(|(tag, _)| tag == name) {
+      Some((_, old)) => *old = value.to_string(),
+      None => self.tags.push((name.to_string(), value.to_string())),
+    }
+  }
+
+  //state
+  pub fn moves(&self) -> &[MoveInput] {
+    &self.moves
+  }
+
+  pub fn tag(&self, name: &str) -> Option<&str> {
+    self
+      .tags
+      .iter()
+      .find(|(tag, _)| tag == name)
+      .map(|(_, value)| value.as_str())
+  }
+
+  //a recorded result wins over the status of the final position
+  pub fn result_token(&self) -> &str {
+    if let Some(result) = &self.result {
+      return result;
+    }
+
+    self.status().result()
+  }
+
+  pub fn status(&self) -> GameStatus {
+    self.status.unwrap_or_else(|| self.board.get_status())
+  }
+
+  //export
+  pub fn to_pgn(&self) -> String {
+    let mut pgn = String::new();
+    let result = self.result_token();
+
+    for (name, default) in SEVEN_TAG_ROSTER {
+      let value = if name == "Result" {
+        result
+      } else {
+        self.tag(name).unwrap_or(default)
+      };
+      push_tag(&mut pgn, name, value);
+    }
+
+    let fen = self.start.to_fen();
+    if fen != Board::default().to_fen() {
+      push_tag(&mut pgn, "SetUp", "1");
+      push_tag(&mut pgn, "FEN", &fen);
+    }
+    let status = self.status();
+    if status.is_over() {
+      let termination = match status {
+        GameStatus::Timeout { .. } => "time forfeit",
+        _ => "normal",
+      };
+      push_tag(&mut pgn, "Termination", termination);
+    }
+    for (name, value) in &self.tags {
+      let roster = SEVEN_TAG_ROSTER.iter().any(|(tag, _)| tag == name);
+      let generated = ["SetUp", "FEN"].contains(&name.as_str())
+        || (status.is_over() && name == "Termination");
+      if !roster && !generated {
+        push_tag(&mut pgn, name, value);
+      }
+    }
+    pgn.push('\n');
+
+    let mut tokens = Vec::new();
+    let mut board = self.start.clone();
+    for (i, input) in self.moves.iter().enumerate() {
+      let san = board.to_san(input).expect("pushed moves are legal");
+      if board.white_turn {
+        tokens.push(format!("{}.", board.fullmove_number()));
+      } else if i == 0 {
+        tokens.push(format!("{}...", board.fullmove_number()));
+      }
+      tokens.push(san);
+      board.move_piece(*input);
+    }
+    if status.is_over() {
+      tokens.push(format!("{{{status}}}"));
+    }
+    tokens.push(result.to_string());
+
+    let mut line = String::new();
+    for token in tokens {
+      if !line.is_empty() && line.len() + 1 + token.len() > LINE_WIDTH {
+        pgn.push_str(&line);
+        pgn.push('\n');
+        line.clear();
+      }
+      if !line.is_empty() {
+        line.push(' ');
+      }
+      line.push_str(&token);
+    }
+    pgn.push_str(&line);
+    pgn.push('\n');
+    pgn
+  }
+
+  //import
+  //first game of the text
+  pub fn parse(text: &str) -> Result<PgnGame, PgnError> {
+    let mut tokens = Lexer::new(text).peekable();
+    read_game(&mut tokens).unwrap_or(Err(PgnError::Empty))
+  }
+
+  //a broken game does not stop the games after it from being read
+  pub fn parse_all(text: &str) -> Vec<Result<PgnGame, PgnError>> {
+    let mut tokens = Lexer::new(text).peekable();
+    let mut games = Vec::new();
+    while let Some(game) = read_game(&mut tokens) {
+      games.push(game);
+    }
+    games
+  }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Token<'a> {
+  Tag(&'a str, String),
+  San(&'a str),
+  Nag,
+  Result(&'a str),
+  Open,
+  Close,
+}
+
+struct Lexer<'a> {
+  text: &'a str,
+  pos: usize,
+}
+
+impl<'a> Lexer<'a> {
+  fn new(text: &'a str) -> Lexer<'a> {
+    Lexer { text, pos: 0 }
+  }
+
+  fn skip_line(&mut self) {
+    self.pos = self.text[self.pos..]
+      .find('\n')
+      .map_or(self.text.len(), |i| self.pos + i);
+  }
+
+  fn tag(&mut self, start: usize) -> Result<Token<'a>, PgnError> {
+    let bytes = self.text.as_bytes();
+    let mut pos = start + 1;
+    let text = self.text;
+    let skip_whitespace = |pos: &mut usize| *pos += whitespace_len(&text[*pos..]);
+
+    skip_whitespace(&mut pos);
+    let name_start = pos;
+    while pos < bytes.len() && (bytes[pos].is_ascii_alphanumeric() || bytes[pos] == b'_') {
+      pos += 1;
+    }
+    let name = &self.text[name_start..pos];
+
+    skip_whitespace(&mut pos);
+    if name.is_empty() || bytes.get(pos) != Some(&b'"') {
+      self.skip_line();
+      return Err(PgnError::Tag(start));
+    }
+    pos += 1;
+
+    let mut value = String::new();
+    loop {
+      match bytes.get(pos) {
+        None => {
+          self.pos = self.text.len();
+          return Err(PgnError::UnterminatedTag(start));
+        }
+        Some(b'"') => break,
+        Some(b'\\') if matches!(bytes.get(pos + 1), Some(b'"' | b'\\')) => {
+          value.push(bytes[pos + 1] as char);
+          pos += 2;
+        }
+        Some(_) => {
+          let c = self.text[pos..].chars().next().unwrap_or_default();
+          value.push(c);
+          pos += c.len_utf8();
+        }
+      }
+    }
+    pos += 1;
+
+    skip_whitespace(&mut pos);
+    match bytes.get(pos) {
+      Some(b']') => {
+        self.pos = pos + 1;
+        Ok(Token::Tag(name, value))
+      }
+      None => {
+        self.pos = self.text.len();
+        Err(PgnError::UnterminatedTag(start))
+      }
+      Some(_) => {
+        self.skip_line();
+        Err(PgnError::Tag(start))
+      }
+    }
+  }
+
+  fn word(&mut self, start: usize) -> Option<Token<'a>> {
+    let rest = &self.text[start..];
+    let len = rest
+      .find(|c: char| c.is_whitespace() || "{}()[];$".contains(c))
+      .unwrap_or(rest.len());
+    //always move on by at least one char, whatever stopped the word
+    let end = start + len.max(rest.chars().next().map_or(0, char::len_utf8));
+    self.pos = end;
+
+    let word = &self.text[start..end];
+    if RESULTS.contains(&word) {
+      return Some(Token::Result(word));
+    }
+
+    //move numbers like 12. and 12... can be glued to the move
+    let san = word.trim_start_matches(|c: char| c.is_ascii_digit());
+    let san = if san.len() < word.len() && san.starts_with('.') {
+      san.trim_start_matches('.')
+    } else {
+      word
+    };
+
+    //standalone annotations like !? carry no move
+    if san.is_empty() || san.chars().all(|c| c == '!' || c == '?') {
+      return None;
+    }
+    Some(Token::San(san))
+  }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+  type Item = Result<(usize, Token<'a>), PgnError>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let bytes = self.text.as_bytes();
+    loop {
+      self.pos += whitespace_len(&self.text[self.pos..]);
+      let start = self.pos;
+      let line_start = start == 0 || bytes[start - 1] == b'\n';
+
+      let token = match bytes.get(start)? {
+        b'{' => {
+          let Some(end) = self.text[start..].find('}') else {
+            self.pos = self.text.len();
+            return Some(Err(PgnError::UnterminatedComment(start)));
+          };
+          self.pos = start + end + 1;
+          continue;
+        }
+        b';' => {
+          self.skip_line();
+          continue;
+        }
+        b'%' if line_start => {
+          self.skip_line();
+          continue;
+        }
+        b'[' => self.tag(start),
+        b'(' => {
+          self.pos += 1;
+          Ok(Token::Open)
+        }
+        b')' => {
+          self.pos += 1;
+          Ok(Token::Close)
+        }
+        b'$' => {
+          self.pos += 1;
+          let digits = bytes[self.pos..].iter().take_while(|c| c.is_ascii_digit()).count();
+          self.pos += digits;
+          if digits == 0 {
+            Err(PgnError::Token(start))
+          } else {
+            Ok(Token::Nag)
+          }
+        }
+        b'}' | b']' => {
+          self.pos += 1;
+          Err(PgnError::Token(start))
+        }
+        _ => match self.word(start) {
+          Some(token) => Ok(token),
+          None => continue,
+        },
+      };
+      return Some(token.map(|token| (start, token)));
+    }
+  }
+}
+
+//unicode spaces count too, pgn pasted from web pages is full of nbsp
+fn whitespace_len(text: &str) -> usize {
+  text.find(|c: char| !c.is_whitespace()).unwrap_or(text.len())
+}
+
+fn push_tag(pgn: &mut String, name: &str, value: &str) {
+  let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+  pgn.push_str(&format!("[{name} \"{value}\"]\n"));
+}
+
+fn start_board(tags: &[(String, String)]) -> Result<Board, PgnError> {
+  match tags.iter().find(|(name, _)| name == "FEN") {
+    Some((_, fen)) => Board::try_from_fen(fen).map_err(PgnError::Fen),
+    None => Ok(Board::default()),
+  }
+}
+
+//a variation starts from the position before the move it replaces
+struct Variation {
+  board: Board,
+  //position before the last move of the variation, nested variations start there
+  before: Option<Board>,
+  ply: usize,
+}
+
+//reads tags and movetext up to the result token, the next tag section or the end of the text
+//variations are replayed to check their moves, only the mainline is kept
+fn read_game(tokens: &mut Peekable<Lexer>) -> Option<Result<PgnGame, PgnError>> {
+  tokens.peek()?;
+
+  let mut tags = Vec::new();
+  //created once the movetext starts, when all tags are known
+  let mut game: Option<PgnGame> = None;
+  let mut result = None;
+  let mut error = None;
+  //position before the last mainline move
+  let mut before = None;
+  let mut variations: Vec<(usize, Variation)> = Vec::new();
+
+  while let Some(token) = tokens.peek() {
+    if game.is_some() && matches!(token, Ok((_, Token::Tag(..)))) {
+      break;
+    }
+
+    let (offset, token) = match tokens.next()? {
+      Ok(token) => token,
+      Err(e) => {
+        error.get_or_insert(e);
+        continue;
+      }
+    };
+
+    if game.is_none() && !matches!(token, Token::Tag(..)) {
+      let start = start_board(&tags).unwrap_or_else(|e| {
+        error.get_or_insert(e);
+        Board::default()
+      });
+      game = Some(PgnGame::new(start));
+    }
+
+    match token {
+      Token::Tag(name, value) => tags.push((name.to_string(), value)),
+      Token::Nag => {}
+      Token::Open => {
+        let Some(game) = game.as_ref() else {
+          continue;
+        };
+        let (parent, ply) = match variations.last() {
+          Some((_, variation)) => (variation.before.clone(), variation.ply),
+          None => (before.clone(), game.moves.len()),
+        };
+        if parent.is_none() {
+          error.get_or_insert(PgnError::Variation(offset));
+        }
+        let variation = Variation {
+          board: parent.unwrap_or_else(|| game.board.clone()),
+          before: None,
+          ply: ply.saturating_sub(1),
+        };
+        variations.push((offset, variation));
+      }
+      Token::Close => {
+        if variations.pop().is_none() {
+          error.get_or_insert(PgnError::UnbalancedVariation(offset));
+        }
+      }
+      Token::Result(token) => {
+        result = Some(token.to_string());
+        break;
+      }
+      Token::San(san) if error.is_none() => {
+        let Some(game) = game.as_mut() else {
+          continue;
+        };
+        let played = match variations.last_mut() {
+          Some((_, variation)) => {
+            variation.ply += 1;
+            let previous = variation.board.clone();
+            let played = variation.board.parse_san(san).and_then(|input| {
+              let legal = variation.board.move_piece(input);
+              if legal { Ok(()) } else { Err(SanError::Illegal) }
+            });
+            variation.before = Some(previous);
+            played.map_err(|reason| (variation.ply, reason))
+          }
+          None => {
+            let ply = game.moves.len() + 1;
+            let previous = game.board.clone();
+            let played = game.board.parse_san(san).and_then(|input| {
+              if game.push(input) { Ok(()) } else { Err(SanError::Illegal) }
+            });
+            before = Some(previous);
+            played.map_err(|reason| (ply, reason))
+          }
+        };
+        if let Err((ply, reason)) = played {
+          error = Some(PgnError::IllegalMove {
+            ply,
+            san: san.to_string(),
+            reason,
+          });
+        }
+      }
+      Token::San(_) => {}
+    }
+  }
+
+  if let Some(&(offset, _)) = variations.first() {
+    error.get_or_insert(PgnError::UnbalancedVariation(offset));
+  }
+  if let Some(error) = error {
+    return Some(Err(error));
+  }
+
+  let mut game = match game {
+    Some(game) => game,
+    None => match start_board(&tags) {
+      Ok(start) => PgnGame::new(start),
+      Err(e) => return Some(Err(e)),
+    },
+  };
+  game.tags = tags;
+  game.result = result;
+  Some(Ok(game))
+}
+
+#[cfg(test)]
+mod tests {
+  mod reading {
+    use crate::board::{Board, pgn::PgnGame};
+
+    pub(super) const OPERA: &str = r#"[Event "Paris"]
+[Site "Paris FRA"]
+[Date "1858.??.??"]
+[Round "?"]
+[White "Paul Morphy"]
+[Black "Duke Karl / Count Isouard"]
+[Result "1-0"]
+
+1.e4 e5 2.Nf3 d6 3.d4 Bg4 {This is a weak move already.} 4.dxe5 Bxf3 5.Qxf3 dxe5
+6.Bc4 Nf6 7.Qb3 Qe7 8.Nc3 c6 9.Bg5 {Black is in what's like a zugzwang position
+here.} b5 10.Nxb5 cxb5 11.Bxb5+ Nbd7 12.O-O-O Rd8 13.Rxd7 Rxd7 14.Rd1 Qe6
+15.Bxd7+ Nxd7 16.Qb8+ Nxb8 17.Rd8# 1-0
+"#;
+
+    #[test]
+    fn full_game() {
+      let game = PgnGame::parse(OPERA).unwrap();
+      assert_eq!(game.tags.len(), 7);
+      assert_eq!(game.tag("White"), Some("Paul Morphy"));
+      assert_eq!(game.tag("Black"), Some("Duke Karl / Count Isouard"));
+      assert_eq!(game.tag("ECO"), None);
+      assert_eq!(game.moves.len(), 33);
+      assert_eq!(game.result.as_deref(), Some("1-0"));
+      assert_eq!(game.board.to_fen(), "1n1Rkb1r/p4ppp/4q3/4p1B1/4P3/8/PPP2PPP/2K5 b k - 1 17");
+    }
+
+    #[test]
+    fn annotations() {
+      let pgn = "1. e4 $1 e5!? ; a line comment\n2. Nf3 {a comment} (2. f4 exf4 (2... d5) 3. Nf3) \
+                 2... Nc6 !! 3. Bb5 a6 4. Ba4 *";
+      let game = PgnGame::parse(pgn).unwrap();
+      assert_eq!(game.moves.len(), 7);
+      assert_eq!(game.result.as_deref(), Some("*"));
+
+      let mut board = Board::default();
+      for input in &game.moves {
+        assert!(board.move_piece(*input));
+      }
+      assert_eq!(board.to_fen(), game.board.to_fen());
+    }
+
+    #[test]
+    fn unicode_whitespace() {
+      //a no-break space and an ideographic space between the moves
+      let pgn = "[Event\u{a0}\"x\"]\n1.\u{a0}e4\u{a0}e5 2.\u{3000}Nf3\u{3000}*";
+      let game = PgnGame::parse(pgn).unwrap();
+      assert_eq!(game.tag("Event"), Some("x"));
+      assert_eq!(game.moves.len(), 3);
+      assert_eq!(game.result.as_deref(), Some("*"));
+    }
+
+    #[test]
+    fn escaped_tags() {
+      let game = PgnGame::parse("[Event \"The \\\"Big\\\" one [2]\"]\n\n1. d4 1/2-1/2").unwrap();
+      assert_eq!(game.tag("Event"), Some("The \"Big\" one [2]"));
+      assert_eq!(game.result.as_deref(), Some("1/2-1/2"));
+    }
+
+    #[test]
+    fn fen_tag() {
+      let pgn = "[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 1\"]\n\n1... Kd7 2. e4 0-1";
+      let game = PgnGame::parse(pgn).unwrap();
+      assert_eq!(game.moves.len(), 2);
+      assert_eq!(game.board.to_fen(), "8/3k4/8/8/4P3/8/8/4K3 b - e3 0 2");
+    }
+
+    #[test]
+    fn no_result() {
+      let game = PgnGame::parse("1. e4 e5 2. Nf3").unwrap();
+      assert_eq!(game.moves.len(), 3);
+      assert_eq!(game.result, None);
+    }
+
+    #[test]
+    fn several_games() {
+      let pgn = "[Event \"a\"]\n\n1. e4 1-0\n\n\
+        [Event \"b\"]\n\n1. d4 d5 0-1\n\n\
+        [Event \"c\"]\n\n1. c4";
+      let games = PgnGame::parse_all(pgn);
+      assert_eq!(games.len(), 3);
+
+      let moves: Vec<usize> = games.iter().map(|g| g.as_ref().unwrap().moves.len()).collect();
+      assert_eq!(moves, vec![1, 2, 1]);
+      assert_eq!(games[2].as_ref().unwrap().tag("Event"), Some("c"));
+    }
+  }
+
+  mod writing {
+    use crate::board::{
+      Board, move_input::MoveInput, pgn::PgnGame, piece::Color, status::GameStatus,
+    };
+
+    fn record(start: Board, uci: &[&str]) -> PgnGame {
+      let mut game = PgnGame::new(start);
+      for uci in uci {
+        assert!(game.push(MoveInput::from_uci(uci).unwrap()));
+      }
+      game
+    }
+
+    #[test]
+    fn illegal_push() {
+      let start = Board::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1");
+      let mut game = record(start, &["e1d2"]);
+      assert!(game.push(MoveInput::from_uci("e8d8").unwrap()));
+      assert!(!game.push(MoveInput::from_uci("a7a8").unwrap()));
+      assert!(!game.push(MoveInput::from_uci("d2d4").unwrap()));
+      assert!(game.push(MoveInput::from_uci("a7a8q").unwrap()));
+
+      assert_eq!(game.moves().len(), 3);
+      assert!(game.to_pgn().ends_with("1. Kd2 Kd8 2. a8=Q+ *\n"));
+    }
+
+    #[test]
+    fn seven_tag_roster() {
+      let mut game = record(Board::default(), &["e2e4", "e7e5", "g1f3"]);
+      game.set_tag("Annotator", "me");
+      game.set_tag("White", "Someone");
+      game.set_tag("White", "Someone \"Else\"");
+
+      assert_eq!(
+        game.to_pgn(),
+        "[Event \"?\"]\n[Site \"?\"]\n[Date \"????.??.??\"]\n[Round \"?\"]\n\
+         [White \"Someone \\\"Else\\\"\"]\n[Black \"?\"]\n[Result \"*\"]\n\
+         [Annotator \"me\"]\n\n1. e4 e5 2. Nf3 *\n"
+      );
+    }
+
+    #[test]
+    fn result_from_status() {
+      let moves = ["e2e4", "e7e5", "f1c4", "b8c6", "d1h5", "g8f6", "h5f7"];
+      let game = record(Board::default(), &moves);
+      assert_eq!(game.result_token(), "1-0");
+      assert!(game.to_pgn().ends_with("4. Qxf7# {White wins by checkmate} 1-0\n"));
+      assert!(game.to_pgn().contains("[Result \"1-0\"]"));
+
+      let mut game = record(Board::default(), &["e2e4"]);
+      game.result = Some("1/2-1/2".to_string());
+      assert!(game.to_pgn().ends_with("1. e4 1/2-1/2\n"));
+    }
+
+    #[test]
+    fn termination() {
+      let mut game = record(Board::default(), &["e2e4", "e7e5"]);
+      game.finish(GameStatus::Resignation { winner: Color::Black });
+      let pgn = game.to_pgn();
+      assert!(pgn.contains("[Result \"0-1\"]\n[Termination \"normal\"]\n"));
+      assert!(pgn.ends_with("1. e4 e5 {Black wins by resignation} 0-1\n"));
+
+      game.finish(GameStatus::Timeout { winner: Color::White });
+      assert!(game.to_pgn().contains("[Termination \"time forfeit\"]"));
+
+      let moves = ["f2f3", "e7e5", "g2g4", "d8h4"];
+      let game = record(Board::default(), &moves);
+      assert!(game.to_pgn().ends_with("2. g4 Qh4# {Black wins by checkmate} 0-1\n"));
+    }
+
+    #[test]
+    fn custom_start() {
+      let start = Board::from_fen("4k3/8/8/8/8/8/4P3/4K3 b - - 0 12");
+      let game = record(start, &["e8d7", "e2e4"]);
+      let pgn = game.to_pgn();
+      assert!(pgn.contains("[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 12\"]\n"));
+      assert!(pgn.ends_with("12... Kd7 13. e4 *\n"));
+    }
+
+    #[test]
+    fn line_width() {
+      let moves = ["g1f3", "g8f6", "f3g1", "f6g8"].repeat(10);
+      let game = record(Board::default(), &moves);
+      let pgn = game.to_pgn();
+      let movetext = pgn.split("\n\n").nth(1).unwrap();
+      assert!(movetext.lines().count() > 1);
+      assert!(movetext.lines().all(|line| line.len() <= 80));
+    }
+
+    #[test]
+    fn round_trip() {
+      let game = PgnGame::parse(super::reading::OPERA).unwrap();
+      let written = game.to_pgn();
+      let read = PgnGame::parse(&written).unwrap();
+
+      assert_eq!(read.moves, game.moves);
+      assert_eq!(read.tag("Black"), game.tag("Black"));
+      assert_eq!(read.tag("Termination"), Some("normal"));
+      assert!(written.ends_with("17. Rd8# {White wins by checkmate} 1-0\n"));
+      assert_eq!(read.to_pgn(), written);
+    }
+  }
+
+  mod errors {
+    use crate::board::{pgn::PgnGame, pgn_error::PgnError, san_error::SanError};
+
+    #[test]
+    fn illegal_move() {
+      let error = PgnGame::parse("1. e4 e5 2. Ke3 Nc6 1-0").err().unwrap();
+      assert_eq!(
+        error,
+        PgnError::IllegalMove {
+          ply: 3,
+          san: "Ke3".to_string(),
+          reason: SanError::Illegal,
+        }
+      );
+      assert_eq!(error.to_string(), "Illegal move Ke3 at ply 3: No legal move matches");
+    }
+
+    #[test]
+    fn ambiguous_move() {
+      let error = PgnGame::parse("1. d3 d6 2. Nf3 Nf6 3. Nd2 *").err().unwrap();
+      assert!(matches!(
+        error,
+        PgnError::IllegalMove {
+          ply: 5,
+          reason: SanError::Ambiguous,
+          ..
+        }
+      ));
+    }
+
+    #[test]
+    fn illegal_move_in_variation() {
+      let error = PgnGame::parse("1. e4 e5 2. Nf3 (2. Ke3) Nc6 *").err().unwrap();
+      assert_eq!(
+        error,
+        PgnError::IllegalMove {
+          ply: 3,
+          san: "Ke3".to_string(),
+          reason: SanError::Illegal,
+        }
+      );
+
+      //the nested variation replaces exf4, so it starts after 2. f4
+      let pgn = "1. e4 e5 2. Nf3 (2. f4 exf4 (2... Qe7 3. Qe3) 3. Nf3) Nc6 *";
+      assert!(matches!(
+        PgnGame::parse(pgn).err(),
+        Some(PgnError::IllegalMove { ply: 5, .. })
+      ));
+      assert_eq!(PgnGame::parse("(1. d4) 1. e4 *").err(), Some(PgnError::Variation(0)));
+    }
+
+    #[test]
+    fn broken_game_is_skipped() {
+      let pgn = "[Event \"a\"]\n\n1. e5 1-0\n\n[Event \"b\"]\n\n1. d4 0-1";
+      let games = PgnGame::parse_all(pgn);
+      assert_eq!(games.len(), 2);
+      assert!(matches!(games[0], Err(PgnError::IllegalMove { ply: 1, .. })));
+      assert_eq!(games[1].as_ref().unwrap().moves.len(), 1);
+    }
+
+    #[test]
+    fn syntax() {
+      assert_eq!(PgnGame::parse("").err(), Some(PgnError::Empty));
+      assert_eq!(PgnGame::parse("1. e4 {oops").err(), Some(PgnError::UnterminatedComment(6)));
+      assert_eq!(PgnGame::parse("1. e4 (1. d4 *").err(), Some(PgnError::UnbalancedVariation(6)));
+      assert_eq!(PgnGame::parse("1. e4 ) *").err(), Some(PgnError::UnbalancedVariation(6)));
+      assert_eq!(PgnGame::parse("[Event]\n1. e4 *").err(), Some(PgnError::Tag(0)));
+      assert_eq!(PgnGame::parse("[Event \"a").err(), Some(PgnError::UnterminatedTag(0)));
+      assert_eq!(PgnGame::parse("1. e4 $ *").err(), Some(PgnError::Token(6)));
+    }
+
+    #[test]
+    fn bad_fen_tag() {
+      let error = PgnGame::parse("[FEN \"8/8 w - - 0 1\"]\n\n*").err().unwrap();
+      assert!(matches!(error, PgnError::Fen(_)));
+    }
+  }
+}
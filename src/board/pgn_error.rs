@@ -0,0 +1,39 @@
+use std::fmt;
+
+use super::{fen_error::FenError, san_error::SanError};
+
+//offsets are byte offsets into the pgn text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PgnError {
+  Empty,
+  Tag(usize),
+  UnterminatedTag(usize),
+  UnterminatedComment(usize),
+  UnbalancedVariation(usize),
+  //a variation before any move it could replace
+  Variation(usize),
+  Token(usize),
+  Fen(FenError),
+  //ply counts from 1 at the first move of the game
+  IllegalMove { ply: usize, san: String, reason: SanError },
+}
+
+impl fmt::Display for PgnError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      PgnError::Empty => write!(f, "No game found"),
+      PgnError::Tag(offset) => write!(f, "Malformed tag pair at byte {offset}"),
+      PgnError::UnterminatedTag(offset) => write!(f, "Unterminated tag pair at byte {offset}"),
+      PgnError::UnterminatedComment(offset) => write!(f, "Unterminated comment at byte {offset}"),
+      PgnError::UnbalancedVariation(offset) => write!(f, "Unbalanced variation at byte {offset}"),
+      PgnError::Variation(offset) => write!(f, "Variation without a move at byte {offset}"),
+      PgnError::Token(offset) => write!(f, "Unexpected token at byte {offset}"),
+      PgnError::Fen(error) => write!(f, "Invalid FEN tag: {error}"),
+      PgnError::IllegalMove { ply, san, reason } => {
+        write!(f, "Illegal move {san} at ply {ply}: {reason}")
+      }
+    }
+  }
+}
+
+impl std::error::Error for PgnError {}
@@ -0,0 +1,100 @@
+use std::ops::Not;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Color {
+  White,
+  Black,
+}
+
+impl Color {
+  pub fn index(self) -> usize {
+    self as usize
+  }
+}
+
+impl Not for Color {
+  type Output = Color;
+
+  fn not(self) -> Color {
+    match self {
+      Color::White => Color::Black,
+      Color::Black => Color::White,
+    }
+  }
+}
+
+//same order as the bitboards in Pieces
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PieceKind {
+  Pawn,
+  Knight,
+  Bishop,
+  Rook,
+  Queen,
+  King,
+}
+
+impl PieceKind {
+  pub const ALL: [PieceKind; 6] = [
+    PieceKind::Pawn,
+    PieceKind::Knight,
+    PieceKind::Bishop,
+    PieceKind::Rook,
+    PieceKind::Queen,
+    PieceKind::King,
+  ];
+  pub const PROMOTIONS: [PieceKind; 4] = [
+    PieceKind::Queen,
+    PieceKind::Rook,
+    PieceKind::Bishop,
+    PieceKind::Knight,
+  ];
+
+  pub fn index(self) -> usize {
+    self as usize
+  }
+
+  pub fn is_promotion(self) -> bool {
+    PieceKind::PROMOTIONS.contains(&self)
+  }
+
+  //uppercase letter as used by fen and san
+  pub fn letter(self) -> char {
+    b"PNBRQK"[self.index()] as char
+  }
+
+  //either case, the case is the colour in fen
+  pub fn from_letter(letter: char) -> Option<PieceKind> {
+    let index = "PNBRQK".find(letter.to_ascii_uppercase())?;
+    Some(PieceKind::ALL[index])
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  mod letters {
+    use crate::board::piece::{Color, PieceKind};
+
+    #[test]
+    fn round_trip() {
+      for kind in PieceKind::ALL {
+        assert_eq!(PieceKind::from_letter(kind.letter()), Some(kind));
+        assert_eq!(PieceKind::from_letter(kind.letter().to_ascii_lowercase()), Some(kind));
+      }
+      assert_eq!(PieceKind::from_letter('x'), None);
+    }
+
+    #[test]
+    fn promotions() {
+      assert!(PieceKind::Knight.is_promotion());
+      assert!(!PieceKind::Pawn.is_promotion());
+      assert!(!PieceKind::King.is_promotion());
+    }
+
+    #[test]
+    fn colors() {
+      assert_eq!(!Color::White, Color::Black);
+      assert_eq!(Color::Black.index(), 1);
+    }
+  }
+}
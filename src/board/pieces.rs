@@ -1,162 +1,150 @@
-use super::util_fns::{if_bool, mask_from_bool};
+use super::{bitboard::Bitboard, piece::PieceKind};
 
 // rank 8 file h is bit 0, rank 1 file a is bit 63 (so top to bottom, right to left)
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Pieces {
-  pub pawns: u64,
-  pub knights: u64,
-  pub bishops: u64,
-  pub rooks: u64,
-  pub queens: u64,
-  pub king: u64,
+  pub pawns: Bitboard,
+  pub knights: Bitboard,
+  pub bishops: Bitboard,
+  pub rooks: Bitboard,
+  pub queens: Bitboard,
+  pub king: Bitboard,
 }
 
 //constructors
 impl Pieces {
   pub fn empty() -> Pieces {
     Pieces {
-      pawns: 0,
-      knights: 0,
-      bishops: 0,
-      rooks: 0,
-      queens: 0,
-      king: 0,
+      pawns: Bitboard::EMPTY,
+      knights: Bitboard::EMPTY,
+      bishops: Bitboard::EMPTY,
+      rooks: Bitboard::EMPTY,
+      queens: Bitboard::EMPTY,
+      king: Bitboard::EMPTY,
     }
   }
 
   pub fn white() -> Pieces {
     Pieces {
-      pawns: 0x00_FF_00_00_00_00_00_00,
-      knights: 0x42_00_00_00_00_00_00_00,
-      bishops: 0x24_00_00_00_00_00_00_00,
-      rooks: 0x81_00_00_00_00_00_00_00,
-      queens: 0x10_00_00_00_00_00_00_00,
-      king: 0x08_00_00_00_00_00_00_00,
+      pawns: Bitboard(0x00_FF_00_00_00_00_00_00),
+      knights: Bitboard(0x42_00_00_00_00_00_00_00),
+      bishops: Bitboard(0x24_00_00_00_00_00_00_00),
+      rooks: Bitboard(0x81_00_00_00_00_00_00_00),
+      queens: Bitboard(0x10_00_00_00_00_00_00_00),
+      king: Bitboard(0x08_00_00_00_00_00_00_00),
     }
   }
 
   pub fn black() -> Pieces {
     Pieces {
-      pawns: 0xFF_00,
-      knights: 0x42,
-      bishops: 0x24,
-      rooks: 0x81,
-      queens: 0x10,
-      king: 0x08,
+      pawns: Bitboard(0xFF_00),
+      knights: Bitboard(0x42),
+      bishops: Bitboard(0x24),
+      rooks: Bitboard(0x81),
+      queens: Bitboard(0x10),
+      king: Bitboard(0x08),
     }
   }
 }
 
 //auxilary
 impl Pieces {
-  pub fn pieces_as_mut_array(&mut self) -> [&mut u64; 6] {
-    [
-      &mut self.pawns,
-      &mut self.knights,
-      &mut self.bishops,
-      &mut self.rooks,
-      &mut self.queens,
-      &mut self.king,
-    ]
-  }
-
-  pub fn pieces_concat(&self) -> u64 {
+  pub fn get(&self, kind: PieceKind) -> Bitboard {
+    match kind {
+      PieceKind::Pawn => self.pawns,
+      PieceKind::Knight => self.knights,
+      PieceKind::Bishop => self.bishops,
+      PieceKind::Rook => self.rooks,
+      PieceKind::Queen => self.queens,
+      PieceKind::King => self.king,
+    }
+  }
+
+  pub fn get_mut(&mut self, kind: PieceKind) -> &mut Bitboard {
+    match kind {
+      PieceKind::Pawn => &mut self.pawns,
+      PieceKind::Knight => &mut self.knights,
+      PieceKind::Bishop => &mut self.bishops,
+      PieceKind::Rook => &mut self.rooks,
+      PieceKind::Queen => &mut self.queens,
+      PieceKind::King => &mut self.king,
+    }
+  }
+
+  //squares whose occupant differs between the two sets
+  pub fn changed(&self, other: &Pieces) -> Bitboard {
+    PieceKind::ALL
+      .into_iter()
+      .fold(Bitboard::EMPTY, |changed, kind| changed | (self.get(kind) ^ other.get(kind)))
+  }
+
+  pub fn pieces_concat(&self) -> Bitboard {
     self.pawns | self.knights | self.bishops | self.rooks | self.queens | self.king
   }
+
+  pub fn kind_at(&self, at: Bitboard) -> Option<PieceKind> {
+    PieceKind::ALL
+      .into_iter()
+      .find(|&kind| self.get(kind).intersects(at))
+  }
 }
 
 //moves & state
 impl Pieces {
-  pub fn move_piece(&mut self, from_mask: u64, to_mask: u64) {
+  pub fn move_piece(&mut self, from: Bitboard, to: Bitboard) {
     let pieces = self.pieces_concat();
-    let valid = from_mask & pieces > 0 && !pieces & to_mask > 0;
-
-    self.pawns = if_bool(
-      valid && self.is_pawn(from_mask),
-      (self.pawns & !from_mask) | to_mask,
-      self.pawns,
-    );
-    self.knights = if_bool(
-      valid && self.is_knight(from_mask),
-      (self.knights & !from_mask) | to_mask,
-      self.knights,
-    );
-    self.bishops = if_bool(
-      valid && self.is_bishop(from_mask),
-      (self.bishops & !from_mask) | to_mask,
-      self.bishops,
-    );
-    self.rooks = if_bool(
-      valid && self.is_rook(from_mask),
-      (self.rooks & !from_mask) | to_mask,
-      self.rooks,
-    );
-    self.queens = if_bool(
-      valid && self.is_queen(from_mask),
-      (self.queens & !from_mask) | to_mask,
-      self.queens,
-    );
-    self.king = if_bool(
-      valid && self.is_king(from_mask),
-      (self.king & !from_mask) | to_mask,
-      self.king,
-    );
-  }
-
-  pub fn remove_piece(&mut self, at_mask: u64) {
-    for piece in self.pieces_as_mut_array() {
-      *piece &= !at_mask;
-    }
-  }
+    let valid = from.intersects(pieces) && (!pieces).intersects(to);
 
-  pub fn promote_to_knight(&mut self, at_mask: u64) {
-    let valid = mask_from_bool(self.pawns & at_mask > 0 && self.knights & at_mask == 0);
-    self.pawns &= !(valid & at_mask);
-    self.knights |= valid & at_mask;
+    for kind in PieceKind::ALL {
+      let moved = valid && self.get(kind).intersects(from);
+      let bitboard = self.get_mut(kind);
+      *bitboard = ((*bitboard & !from) | to).only_if(moved) | bitboard.only_if(!moved);
+    }
   }
 
-  pub fn promote_to_bishop(&mut self, at_mask: u64) {
-    let valid = mask_from_bool(self.pawns & at_mask > 0 && self.bishops & at_mask == 0);
-    self.pawns &= !(valid & at_mask);
-    self.bishops |= valid & at_mask;
+  pub fn add_piece(&mut self, kind: PieceKind, at: Bitboard) {
+    *self.get_mut(kind) |= at;
   }
 
-  pub fn promote_to_rook(&mut self, at_mask: u64) {
-    let valid = mask_from_bool(self.pawns & at_mask > 0 && self.rooks & at_mask == 0);
-    self.pawns &= !(valid & at_mask);
-    self.rooks |= valid & at_mask;
+  pub fn remove_piece(&mut self, at: Bitboard) {
+    for kind in PieceKind::ALL {
+      *self.get_mut(kind) &= !at;
+    }
   }
 
-  pub fn promote_to_queen(&mut self, at_mask: u64) {
-    let valid = mask_from_bool(self.pawns & at_mask > 0 && self.queens & at_mask == 0);
-    self.pawns &= !(valid & at_mask);
-    self.queens |= valid & at_mask;
+  //pawns on the given squares become the promoted kind
+  pub fn promote(&mut self, kind: PieceKind, at: Bitboard) {
+    let promoted = self.get(kind);
+    let at_mask = at.only_if(self.pawns.intersects(at) && !promoted.intersects(at));
+    self.pawns &= !at_mask;
+    *self.get_mut(kind) |= at_mask;
   }
 
-  pub fn is_empty(&self, at_mask: u64) -> bool {
-    self.pieces_concat() & at_mask == 0
+  pub fn is_empty(&self, at: Bitboard) -> bool {
+    !self.pieces_concat().intersects(at)
   }
 
-  pub fn is_pawn(&self, at_mask: u64) -> bool {
-    self.pawns & at_mask > 0
+  pub fn is_pawn(&self, at: Bitboard) -> bool {
+    self.pawns.intersects(at)
   }
 
-  pub fn is_knight(&self, at_mask: u64) -> bool {
-    self.knights & at_mask > 0
+  pub fn is_knight(&self, at: Bitboard) -> bool {
+    self.knights.intersects(at)
   }
 
-  pub fn is_bishop(&self, at_mask: u64) -> bool {
-    self.bishops & at_mask > 0
+  pub fn is_bishop(&self, at: Bitboard) -> bool {
+    self.bishops.intersects(at)
   }
 
-  pub fn is_rook(&self, at_mask: u64) -> bool {
-    self.rooks & at_mask > 0
+  pub fn is_rook(&self, at: Bitboard) -> bool {
+    self.rooks.intersects(at)
   }
-  pub fn is_queen(&self, at_mask: u64) -> bool {
-    self.queens & at_mask > 0
+  pub fn is_queen(&self, at: Bitboard) -> bool {
+    self.queens.intersects(at)
   }
 
-  pub fn is_king(&self, at_mask: u64) -> bool {
-    self.king & at_mask > 0
+  pub fn is_king(&self, at: Bitboard) -> bool {
+    self.king.intersects(at)
   }
 }
 
@@ -164,13 +152,13 @@ impl Pieces {
 #[cfg(test)]
 mod tests {
   mod movement {
-    use crate::board::pieces::Pieces;
+    use crate::board::{bitboard::Bitboard, pieces::Pieces};
 
     #[test]
     fn valid_move() {
       let mut pieces = Pieces::white();
-      let from = 0x00_80_00_00_00_00_00_00;
-      let to = 0x00_00_80_00_00_00_00_00;
+      let from = Bitboard(0x00_80_00_00_00_00_00_00);
+      let to = Bitboard(0x00_00_80_00_00_00_00_00);
       pieces.move_piece(from, to);
       assert_eq!(pieces.pawns, 0x00_7F_80_00_00_00_00_00);
       assert_eq!(pieces.pieces_concat(), 0xFF_7F_80_00_00_00_00_00);
@@ -179,8 +167,8 @@ mod tests {
     #[test]
     fn zero_from() {
       let mut pieces = Pieces::white();
-      let from = 0;
-      let to = 0x00_00_80_00_00_00_00_00;
+      let from = Bitboard(0);
+      let to = Bitboard(0x00_00_80_00_00_00_00_00);
       pieces.move_piece(from, to);
       assert_eq!(pieces.pawns, 0x00_FF_00_00_00_00_00_00);
       assert_eq!(pieces.pieces_concat(), 0xFF_FF_00_00_00_00_00_00);
@@ -189,8 +177,8 @@ mod tests {
     #[test]
     fn empty_from() {
       let mut pieces = Pieces::white();
-      let from = 1;
-      let to = 0x00_00_80_00_00_00_00_00;
+      let from = Bitboard(1);
+      let to = Bitboard(0x00_00_80_00_00_00_00_00);
       pieces.move_piece(from, to);
       assert_eq!(pieces.pawns, 0x00_FF_00_00_00_00_00_00);
       assert_eq!(pieces.pieces_concat(), 0xFF_FF_00_00_00_00_00_00);
@@ -199,8 +187,8 @@ mod tests {
     #[test]
     fn zero_to() {
       let mut pieces = Pieces::white();
-      let from = 0x00_80_00_00_00_00_00_00;
-      let to = 0;
+      let from = Bitboard(0x00_80_00_00_00_00_00_00);
+      let to = Bitboard(0);
       pieces.move_piece(from, to);
       assert_eq!(pieces.pawns, 0x00_FF_00_00_00_00_00_00);
       assert_eq!(pieces.pieces_concat(), 0xFF_FF_00_00_00_00_00_00);
@@ -209,8 +197,8 @@ mod tests {
     #[test]
     fn occupied_to() {
       let mut pieces = Pieces::white();
-      let from = 0x00_80_00_00_00_00_00_00;
-      let to = 0x80_00_00_00_00_00_00_00;
+      let from = Bitboard(0x00_80_00_00_00_00_00_00);
+      let to = Bitboard(0x80_00_00_00_00_00_00_00);
       pieces.move_piece(from, to);
       assert_eq!(pieces.pawns, 0x00_FF_00_00_00_00_00_00);
       assert_eq!(pieces.pieces_concat(), 0xFF_FF_00_00_00_00_00_00);
@@ -219,8 +207,8 @@ mod tests {
     #[test]
     fn from_eq_to() {
       let mut pieces = Pieces::white();
-      let from = 0x00_80_00_00_00_00_00_00;
-      let to = 0x00_80_00_00_00_00_00_00;
+      let from = Bitboard(0x00_80_00_00_00_00_00_00);
+      let to = Bitboard(0x00_80_00_00_00_00_00_00);
       pieces.move_piece(from, to);
       assert_eq!(pieces.pawns, 0x00_FF_00_00_00_00_00_00);
       assert_eq!(pieces.pieces_concat(), 0xFF_FF_00_00_00_00_00_00);
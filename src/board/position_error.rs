@@ -0,0 +1,52 @@
+use super::bitboard::Bitboard;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PositionError {
+  KingCount { white: bool, count: u32 },
+  PawnCount { white: bool, count: u32 },
+  PieceCount { white: bool, count: u32 },
+  //more promoted pieces than missing pawns
+  PromotedPieces { white: bool, count: u32 },
+  PawnOnBackRank(Bitboard),
+  OverlappingPieces(Bitboard),
+  OpponentInCheck,
+  CastlingRights { white: bool, short: bool },
+  EnPassant(Bitboard),
+  //the halfmove clock, which a double pawn push would have reset
+  EnPassantClock(u64),
+}
+
+impl fmt::Display for PositionError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let color = |white: bool| if white { "White" } else { "Black" };
+
+    match *self {
+      PositionError::KingCount { white, count } => {
+        write!(f, "{} has {count} kings", color(white))
+      }
+      PositionError::PawnCount { white, count } => {
+        write!(f, "{} has {count} pawns", color(white))
+      }
+      PositionError::PieceCount { white, count } => {
+        write!(f, "{} has {count} pieces", color(white))
+      }
+      PositionError::PromotedPieces { white, count } => {
+        write!(f, "{} has {count} promoted pieces but not enough missing pawns", color(white))
+      }
+      PositionError::PawnOnBackRank(mask) => write!(f, "Pawns on the back rank ({mask:#018x})"),
+      PositionError::OverlappingPieces(mask) => write!(f, "Overlapping pieces ({mask:#018x})"),
+      PositionError::OpponentInCheck => write!(f, "The side not to move is in check"),
+      PositionError::CastlingRights { white, short } => {
+        let side = if short { "short" } else { "long" };
+        write!(f, "{} can't castle {side} without king and rook on their squares", color(white))
+      }
+      PositionError::EnPassant(mask) => write!(f, "Impossible en passant square ({mask:#018x})"),
+      PositionError::EnPassantClock(clock) => {
+        write!(f, "Halfmove clock {clock} right after a double pawn push")
+      }
+    }
+  }
+}
+
+impl std::error::Error for PositionError {}
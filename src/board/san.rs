@@ -0,0 +1,276 @@
+use super::{
+  Board,
+  move_gen::MoveGen,
+  move_input::MoveInput,
+  move_list::Move,
+  piece::PieceKind,
+  san_error::SanError,
+  square::Square,
+};
+
+//pawn moves carry no piece letter
+fn piece_letter(kind: PieceKind) -> Option<char> {
+  (kind != PieceKind::Pawn).then(|| kind.letter())
+}
+
+impl Board {
+  pub fn to_san(&self, input: &MoveInput) -> Option<String> {
+    let moves = self.legal_moves();
+    let m = *moves
+      .iter()
+      .find(|m| m.from == input.from && m.to == input.to && m.promotion == input.promotion)?;
+
+    let mut san = if m.is_castle() {
+      let short = m.to.file() > m.from.file();
+      String::from(if short { "O-O" } else { "O-O-O" })
+    } else {
+      self.san_body(&m, &moves)
+    };
+
+    let mut after = self.clone();
+    after.move_piece(m.input());
+    if MoveGen::default(&after).in_check() {
+      san.push(if after.legal_moves().is_empty() { '#' } else { '+' });
+    }
+
+    Some(san)
+  }
+
+  fn san_body(&self, m: &Move, moves: &[Move]) -> String {
+    let kind = self.piece_at(m.from).map_or(PieceKind::Pawn, |(_, kind)| kind);
+    let mut san = String::new();
+
+    if let Some(letter) = piece_letter(kind) {
+      san.push(letter);
+
+      //other pieces of the same kind that can reach the same square
+      let rivals: Vec<&Move> = moves
+        .iter()
+        .filter(|other| {
+          other.to == m.to
+            && other.from != m.from
+            && self.piece_at(other.from).map(|(_, kind)| kind) == Some(kind)
+        })
+        .collect();
+
+      let same_file = rivals.iter().any(|other| other.from.file() == m.from.file());
+      let same_rank = rivals.iter().any(|other| other.from.rank() == m.from.rank());
+
+      if !rivals.is_empty() && !same_file {
+        san.push(m.from.file_char());
+      } else if !rivals.is_empty() && !same_rank {
+        san.push(m.from.rank_char());
+      } else if !rivals.is_empty() {
+        san.push_str(&m.from.to_string());
+      }
+    } else if m.is_capture() {
+      san.push(m.from.file_char());
+    }
+
+    if m.is_capture() {
+      san.push('x');
+    }
+    san.push_str(&m.to.to_string());
+
+    if let Some(promotion) = m.promotion {
+      san.push('=');
+      san.push(promotion.letter());
+    }
+    san
+  }
+
+  pub fn parse_san(&self, san: &str) -> Result<MoveInput, SanError> {
+    let san = san.trim_end_matches(['+', '#', '!', '?']);
+    let moves = self.legal_moves();
+
+    if matches!(san, "O-O" | "0-0" | "O-O-O" | "0-0-0") {
+      let short = san.len() == 3;
+      return moves
+        .iter()
+        .find(|m| m.is_castle() && (m.to.file() > m.from.file()) == short)
+        .map(|m| m.input())
+        .ok_or(SanError::Illegal);
+    }
+
+    let bytes = san.as_bytes();
+    let (kind, rest) = match bytes.first() {
+      Some(&letter) if letter.is_ascii_uppercase() => (
+        PieceKind::from_letter(letter as char)
+          .filter(|&kind| kind != PieceKind::Pawn)
+          .ok_or(SanError::Syntax)?,
+        &bytes[1..],
+      ),
+      Some(_) => (PieceKind::Pawn, bytes),
+      None => return Err(SanError::Syntax),
+    };
+
+    //promotion piece at the end, with or without '='
+    let (rest, promotion) = match rest {
+      [body @ .., b'=', letter] | [body @ .., letter]
+        if kind == PieceKind::Pawn && letter.is_ascii_uppercase() =>
+      {
+        let promotion = PieceKind::from_letter(*letter as char)
+          .filter(|kind| kind.is_promotion())
+          .ok_or(SanError::Syntax)?;
+        (body, Some(promotion))
+      }
+      _ => (rest, None),
+    };
+
+    let [disambiguation @ .., file, rank] = rest else {
+      return Err(SanError::Syntax);
+    };
+    let to = Square::new(file.wrapping_sub(b'a'), rank.wrapping_sub(b'1')).ok_or(SanError::Syntax)?;
+
+    let disambiguation = match disambiguation {
+      [hint @ .., b'x'] => hint,
+      hint => hint,
+    };
+    let mut from_file = None;
+    let mut from_rank = None;
+    for &c in disambiguation {
+      match c {
+        b'a'..=b'h' if from_file.is_none() => from_file = Some(c - b'a'),
+        b'1'..=b'8' if from_rank.is_none() => from_rank = Some(c - b'1'),
+        _ => return Err(SanError::Syntax),
+      }
+    }
+
+    let candidates: Vec<&Move> = moves
+      .iter()
+      .filter(|m| {
+        m.to == to
+          && m.promotion == promotion
+          && !m.is_castle()
+          && self.piece_at(m.from).map(|(_, kind)| kind) == Some(kind)
+          && from_file.is_none_or(|file| m.from.file() == file)
+          && from_rank.is_none_or(|rank| m.from.rank() == rank)
+      })
+      .collect();
+
+    match candidates.as_slice() {
+      [m] => Ok(m.input()),
+      [] => Err(SanError::Illegal),
+      _ => Err(SanError::Ambiguous),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::board::{
+    Board,
+    move_input::MoveInput,
+    piece::PieceKind,
+    square::Square,
+    san_error::SanError,
+  };
+
+  fn play(board: &mut Board, san: &str) {
+    let input = board.parse_san(san).unwrap();
+    assert!(board.move_piece(input));
+  }
+
+  #[test]
+  fn opening() {
+    let board = Board::default();
+    assert_eq!(board.to_san(&MoveInput::from_id(51, 35).unwrap()).unwrap(), "e4");
+    assert_eq!(board.to_san(&MoveInput::from_id(57, 42).unwrap()).unwrap(), "Nf3");
+    assert_eq!(board.to_san(&MoveInput::from_id(51, 27).unwrap()), None);
+
+    let input = board.parse_san("Nc3").unwrap();
+    assert_eq!((input.from.index(), input.to.index()), (62, 45));
+  }
+
+  #[test]
+  fn disambiguation() {
+    let board = Board::from_fen("4k3/8/8/8/8/8/1N2KN2/R6R w - - 0 1");
+    assert_eq!(board.to_san(&MoveInput::from_id(54, 44).unwrap()).unwrap(), "Nbd3");
+    assert_eq!(board.to_san(&MoveInput::from_id(63, 60).unwrap()).unwrap(), "Rad1");
+
+    let board = Board::from_fen("4k3/8/8/1N6/8/1N6/8/4K3 w - - 0 1");
+    assert_eq!(board.to_san(&MoveInput::from_id(30, 36).unwrap()).unwrap(), "N5d4");
+
+    let board = Board::from_fen("7k/8/8/1Q1Q4/8/1Q1Q4/8/4K3 w - - 0 1");
+    assert_eq!(board.to_san(&MoveInput::from_id(46, 37).unwrap()).unwrap(), "Qb3c4");
+    assert_eq!(board.parse_san("Qb3c4").unwrap().from.index(), 46);
+    assert_eq!(board.parse_san("Qc4").err(), Some(SanError::Ambiguous));
+  }
+
+  #[test]
+  fn castling() {
+    let board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+    assert_eq!(board.to_san(&MoveInput::from_id(59, 57).unwrap()).unwrap(), "O-O");
+    assert_eq!(board.to_san(&MoveInput::from_id(59, 61).unwrap()).unwrap(), "O-O-O");
+    assert_eq!(board.parse_san("O-O").unwrap().to.index(), 57);
+    assert_eq!(board.parse_san("0-0-0").unwrap().to.index(), 61);
+  }
+
+  #[test]
+  fn captures_and_checks() {
+    let mut board = Board::default();
+    for san in ["e4", "d5", "exd5", "Qxd5", "Nc3", "Qe5+"] {
+      play(&mut board, san);
+    }
+    assert_eq!(board.parse_san("Qe2").unwrap().to.index(), 51);
+    assert_eq!(board.parse_san("Qe3").err(), Some(SanError::Illegal));
+  }
+
+  #[test]
+  fn en_passant() {
+    let mut board = Board::from_fen("4k3/3p4/8/4P3/8/8/8/4K3 b - - 0 1");
+    play(&mut board, "d5");
+    assert_eq!(board.to_san(&MoveInput::from_id(27, 20).unwrap()).unwrap(), "exd6");
+  }
+
+  #[test]
+  fn promotion() {
+    let board = Board::from_fen("3k4/4P3/8/8/8/8/8/4K3 w - - 0 1");
+    let e7 = "e7".parse::<Square>().unwrap();
+    let e8 = "e8".parse::<Square>().unwrap();
+    let queen = MoveInput::with_promotion(e7, e8, PieceKind::Queen).unwrap();
+    let knight = MoveInput::with_promotion(e7, e8, PieceKind::Knight).unwrap();
+    assert_eq!(board.to_san(&queen).unwrap(), "e8=Q+");
+    assert_eq!(board.to_san(&knight).unwrap(), "e8=N");
+
+    assert_eq!(board.parse_san("e8=N").unwrap().promotion, Some(PieceKind::Knight));
+    assert_eq!(board.parse_san("e8Q").unwrap().promotion, Some(PieceKind::Queen));
+    assert_eq!(board.parse_san("e8").err(), Some(SanError::Illegal));
+  }
+
+  #[test]
+  fn checkmate() {
+    let mut board = Board::default();
+    for san in ["f3", "e5", "g4"] {
+      play(&mut board, san);
+    }
+    assert_eq!(board.to_san(&MoveInput::from_id(4, 32).unwrap()).unwrap(), "Qh4#");
+  }
+
+  #[test]
+  fn syntax() {
+    let board = Board::default();
+    assert_eq!(board.parse_san("").err(), Some(SanError::Syntax));
+    assert_eq!(board.parse_san("e9").err(), Some(SanError::Syntax));
+    assert_eq!(board.parse_san("Zf3").err(), Some(SanError::Syntax));
+  }
+
+  #[test]
+  fn round_trip() {
+    let fens = [
+      "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+      "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+      "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+      "8/8/1k6/2b5/2pP4/8/5K2/8 b - d3 0 1",
+    ];
+
+    for fen in fens {
+      let board = Board::from_fen(fen);
+      for m in board.legal_moves() {
+        let san = board.to_san(&m.input()).unwrap();
+        let parsed = board.parse_san(&san).unwrap();
+        assert_eq!((parsed.from, parsed.to, parsed.promotion), (m.from, m.to, m.promotion));
+      }
+    }
+  }
+}
@@ -0,0 +1,20 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SanError {
+  Syntax,
+  Illegal,
+  Ambiguous,
+}
+
+impl fmt::Display for SanError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      SanError::Syntax => write!(f, "Not a valid SAN move"),
+      SanError::Illegal => write!(f, "No legal move matches"),
+      SanError::Ambiguous => write!(f, "More than one legal move matches"),
+    }
+  }
+}
+
+impl std::error::Error for SanError {}
@@ -0,0 +1,142 @@
+use super::square_error::ParseSquareError;
+use std::{fmt, str::FromStr};
+
+//wraps the bit layout, index 0 is h8 and index 63 is a1
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Square(u8);
+
+impl Square {
+  //file 0 is the a file, rank 0 is the first rank
+  pub fn new(file: u8, rank: u8) -> Option<Square> {
+    if file > 7 || rank > 7 {
+      return None;
+    }
+    Some(Square((7 - file) + (7 - rank) * 8))
+  }
+
+  pub fn from_index(index: usize) -> Option<Square> {
+    if index > 63 {
+      return None;
+    }
+    Some(Square(index as u8))
+  }
+
+  //only single bit masks are squares
+  pub fn from_mask(mask: u64) -> Option<Square> {
+    if mask.count_ones() != 1 {
+      return None;
+    }
+    Some(Square(mask.trailing_zeros() as u8))
+  }
+
+  pub fn index(self) -> usize {
+    self.0 as usize
+  }
+
+  pub fn mask(self) -> u64 {
+    1 << self.0
+  }
+
+  pub fn file(self) -> u8 {
+    7 - self.0 % 8
+  }
+
+  pub fn rank(self) -> u8 {
+    7 - self.0 / 8
+  }
+
+  pub fn file_char(self) -> char {
+    (b'a' + self.file()) as char
+  }
+
+  pub fn rank_char(self) -> char {
+    (b'1' + self.rank()) as char
+  }
+}
+
+impl fmt::Display for Square {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}{}", self.file_char(), self.rank_char())
+  }
+}
+
+impl FromStr for Square {
+  type Err = ParseSquareError;
+
+  fn from_str(s: &str) -> Result<Square, ParseSquareError> {
+    let &[file, rank] = s.as_bytes() else {
+      return Err(ParseSquareError::Length);
+    };
+    if !(b'a'..=b'h').contains(&file) {
+      return Err(ParseSquareError::File);
+    }
+    if !(b'1'..=b'8').contains(&rank) {
+      return Err(ParseSquareError::Rank);
+    }
+    Square::new(file - b'a', rank - b'1').ok_or(ParseSquareError::Length)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  mod layout {
+    use crate::board::square::Square;
+
+    #[test]
+    fn corners() {
+      assert_eq!(Square::from_index(0).unwrap().to_string(), "h8");
+      assert_eq!(Square::from_index(7).unwrap().to_string(), "a8");
+      assert_eq!(Square::from_index(56).unwrap().to_string(), "h1");
+      assert_eq!(Square::from_index(63).unwrap().to_string(), "a1");
+      assert_eq!(Square::from_index(64), None);
+    }
+
+    #[test]
+    fn file_and_rank() {
+      let e2 = Square::new(4, 1).unwrap();
+      assert_eq!(e2.index(), 51);
+      assert_eq!(e2.mask(), 1 << 51);
+      assert_eq!((e2.file(), e2.rank()), (4, 1));
+      assert_eq!((e2.file_char(), e2.rank_char()), ('e', '2'));
+      assert_eq!(Square::new(8, 0), None);
+    }
+
+    #[test]
+    fn masks() {
+      assert_eq!(Square::from_mask(1 << 35).unwrap().to_string(), "e4");
+      assert_eq!(Square::from_mask(0), None);
+      assert_eq!(Square::from_mask(3), None);
+    }
+  }
+
+  mod parsing {
+    use crate::board::{square::Square, square_error::ParseSquareError};
+
+    #[test]
+    fn round_trip() {
+      for index in 0..64 {
+        let square = Square::from_index(index).unwrap();
+        assert_eq!(square.to_string().parse::<Square>(), Ok(square));
+      }
+    }
+
+    #[test]
+    fn invalid() {
+      for name in ["", "e", "e9", "i1", "E2", "e2e4"] {
+        assert!(name.parse::<Square>().is_err());
+      }
+    }
+
+    #[test]
+    fn errors() {
+      assert_eq!("e2e4".parse::<Square>(), Err(ParseSquareError::Length));
+      assert_eq!("é".parse::<Square>(), Err(ParseSquareError::File));
+      assert_eq!("i1".parse::<Square>(), Err(ParseSquareError::File));
+      assert_eq!("e0".parse::<Square>(), Err(ParseSquareError::Rank));
+      assert_eq!(
+        "e9".parse::<Square>().unwrap_err().to_string(),
+        "The rank must be a digit from 1 to 8"
+      );
+    }
+  }
+}
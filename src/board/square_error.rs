@@ -0,0 +1,20 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseSquareError {
+  Length,
+  File,
+  Rank,
+}
+
+impl fmt::Display for ParseSquareError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ParseSquareError::Length => write!(f, "A square is a file and a rank, like e4"),
+      ParseSquareError::File => write!(f, "The file must be a letter from a to h"),
+      ParseSquareError::Rank => write!(f, "The rank must be a digit from 1 to 8"),
+    }
+  }
+}
+
+impl std::error::Error for ParseSquareError {}
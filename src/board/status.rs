@@ -1,5 +1,108 @@
-//TODO: implement other
-pub const PLAYING: u64 = 0;
-pub const DRAW: u64 = 1;
-pub const WHITE_WON: u64 = 2;
-pub const BLACK_WON: u64 = 4;
+use std::fmt;
+
+use super::piece::Color;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameStatus {
+  Playing,
+  Checkmate { winner: Color },
+  Stalemate,
+  InsufficientMaterial,
+  //seventy-five moves when automatic, fifty when claimed
+  FiftyMove,
+  //fivefold when automatic, threefold when claimed
+  Repetition,
+  //the following are never detected on the board, the caller reports them
+  Resignation { winner: Color },
+  Timeout { winner: Color },
+  AgreedDraw,
+}
+
+impl GameStatus {
+  pub fn is_over(self) -> bool {
+    self != GameStatus::Playing
+  }
+
+  pub fn winner(self) -> Option<Color> {
+    match self {
+      GameStatus::Checkmate { winner }
+      | GameStatus::Resignation { winner }
+      | GameStatus::Timeout { winner } => Some(winner),
+      _ => None,
+    }
+  }
+
+  pub fn is_draw(self) -> bool {
+    self.is_over() && self.winner().is_none()
+  }
+
+  //pgn result token
+  pub fn result(self) -> &'static str {
+    match self.winner() {
+      Some(Color::White) => "1-0",
+      Some(Color::Black) => "0-1",
+      None if self.is_draw() => "1/2-1/2",
+      None => "*",
+    }
+  }
+
+  pub fn reason(self) -> &'static str {
+    match self {
+      GameStatus::Playing => "in progress",
+      GameStatus::Checkmate { .. } => "checkmate",
+      GameStatus::Stalemate => "stalemate",
+      GameStatus::InsufficientMaterial => "insufficient material",
+      GameStatus::FiftyMove => "fifty-move rule",
+      GameStatus::Repetition => "repetition",
+      GameStatus::Resignation { .. } => "resignation",
+      GameStatus::Timeout { .. } => "timeout",
+      GameStatus::AgreedDraw => "agreement",
+    }
+  }
+}
+
+impl fmt::Display for GameStatus {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self.winner() {
+      Some(Color::White) => write!(f, "White wins by {}", self.reason()),
+      Some(Color::Black) => write!(f, "Black wins by {}", self.reason()),
+      None if self.is_draw() => write!(f, "Draw by {}", self.reason()),
+      None => write!(f, "Game in progress"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  mod reporting {
+    use crate::board::{piece::Color, status::GameStatus};
+
+    #[test]
+    fn results() {
+      assert_eq!(GameStatus::Playing.result(), "*");
+      assert_eq!(GameStatus::Checkmate { winner: Color::White }.result(), "1-0");
+      assert_eq!(GameStatus::Timeout { winner: Color::Black }.result(), "0-1");
+      assert_eq!(GameStatus::Stalemate.result(), "1/2-1/2");
+      assert_eq!(GameStatus::AgreedDraw.result(), "1/2-1/2");
+    }
+
+    #[test]
+    fn over() {
+      assert!(!GameStatus::Playing.is_over());
+      assert!(!GameStatus::Playing.is_draw());
+      assert!(GameStatus::Repetition.is_draw());
+      assert!(!GameStatus::Resignation { winner: Color::White }.is_draw());
+      assert_eq!(GameStatus::Resignation { winner: Color::White }.winner(), Some(Color::White));
+    }
+
+    #[test]
+    fn display() {
+      assert_eq!(
+        GameStatus::Checkmate { winner: Color::Black }.to_string(),
+        "Black wins by checkmate"
+      );
+      assert_eq!(GameStatus::FiftyMove.to_string(), "Draw by fifty-move rule");
+      assert_eq!(GameStatus::Playing.to_string(), "Game in progress");
+    }
+  }
+}
@@ -0,0 +1,19 @@
+use super::{bitboard::Bitboard, piece::PieceKind, square::Square};
+
+pub struct Undo {
+  pub from: Square,
+  pub to: Square,
+  pub moved: PieceKind,
+  pub captured: Option<PieceKind>,
+  pub captured_mask: Bitboard,
+  pub rook_from: Bitboard,
+  pub rook_to: Bitboard,
+
+  pub hash: u64,
+  pub en_passant: Bitboard,
+  pub half_clock: u64,
+  pub white_short_castle: bool,
+  pub white_long_castle: bool,
+  pub black_short_castle: bool,
+  pub black_long_castle: bool,
+}
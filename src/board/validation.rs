@@ -0,0 +1,300 @@
+use super::{
+  Board, bitboard::Bitboard, board_movement_trait::BoardMovement, move_gen::MoveGen,
+  pieces::Pieces, position_error::PositionError,
+};
+
+impl Board {
+  pub fn validate(&self) -> Vec<PositionError> {
+    let mut errors = Vec::new();
+
+    for (pieces, white) in [(&self.white, true), (&self.black, false)] {
+      self.validate_material(pieces, white, &mut errors);
+    }
+
+    let back_rank_pawns = self.pawns() & Bitboard(0xFF_00_00_00_00_00_00_FF);
+    if !back_rank_pawns.is_empty() {
+      errors.push(PositionError::PawnOnBackRank(back_rank_pawns));
+    }
+
+    let overlapping = self.overlapping();
+    if !overlapping.is_empty() {
+      errors.push(PositionError::OverlappingPieces(overlapping));
+    }
+
+    let mut opponent = self.clone();
+    opponent.white_turn = !self.white_turn;
+    if MoveGen::default(&opponent).in_check() {
+      errors.push(PositionError::OpponentInCheck);
+    }
+
+    self.validate_castling(&mut errors);
+    self.validate_en_passant(&mut errors);
+    errors
+  }
+
+  fn validate_material(&self, pieces: &Pieces, white: bool, errors: &mut Vec<PositionError>) {
+    let kings = pieces.king.count();
+    if kings != 1 {
+      errors.push(PositionError::KingCount { white, count: kings });
+    }
+
+    let pawns = pieces.pawns.count();
+    if pawns > 8 {
+      errors.push(PositionError::PawnCount { white, count: pawns });
+    }
+
+    let total = pieces.pieces_concat().count();
+    if total > 16 {
+      errors.push(PositionError::PieceCount { white, count: total });
+    }
+
+    let promoted = pieces.knights.count().saturating_sub(2)
+      + pieces.bishops.count().saturating_sub(2)
+      + pieces.rooks.count().saturating_sub(2)
+      + pieces.queens.count().saturating_sub(1);
+    if promoted + pawns > 8 {
+      errors.push(PositionError::PromotedPieces {
+        white,
+        count: promoted,
+      });
+    }
+  }
+
+  fn overlapping(&self) -> Bitboard {
+    let mut seen = Bitboard::EMPTY;
+    let mut overlapping = Bitboard::EMPTY;
+    for pieces in [&self.white, &self.black] {
+      for bitboard in [
+        pieces.pawns,
+        pieces.knights,
+        pieces.bishops,
+        pieces.rooks,
+        pieces.queens,
+        pieces.king,
+      ] {
+        overlapping |= seen & bitboard;
+        seen |= bitboard;
+      }
+    }
+    overlapping
+  }
+
+  fn validate_castling(&self, errors: &mut Vec<PositionError>) {
+    let rights = [
+      (self.white_short_castle, true, true, &self.white, 0x01_00_00_00_00_00_00_00),
+      (self.white_long_castle, true, false, &self.white, 0x80_00_00_00_00_00_00_00),
+      (self.black_short_castle, false, true, &self.black, 0x00_00_00_00_00_00_00_01),
+      (self.black_long_castle, false, false, &self.black, 0x00_00_00_00_00_00_00_80),
+    ];
+
+    for (right, white, short, pieces, rook_u64) in rights {
+      let king_u64 = if white { 0x08_00_00_00_00_00_00_00 } else { 0x08 };
+      let in_place =
+        pieces.king.intersects(Bitboard(king_u64)) && pieces.rooks.intersects(Bitboard(rook_u64));
+
+      if right && !in_place {
+        errors.push(PositionError::CastlingRights { white, short });
+      }
+    }
+  }
+
+  fn validate_en_passant(&self, errors: &mut Vec<PositionError>) {
+    if self.en_passant.is_empty() {
+      return;
+    }
+
+    //the pawn that just advanced two squares stands in front of the target, its origin behind it
+    let (rank, enemy_pawns, pawn, origin) = if self.white_turn {
+      (
+        Bitboard(0x00_00_00_00_00_FF_00_00),
+        self.black.pawns,
+        self.en_passant.move_down_mask(1),
+        self.en_passant.move_up_mask(1),
+      )
+    } else {
+      (
+        Bitboard(0x00_00_FF_00_00_00_00_00),
+        self.white.pawns,
+        self.en_passant.move_up_mask(1),
+        self.en_passant.move_down_mask(1),
+      )
+    };
+
+    let valid = self.en_passant.count() == 1
+      && self.en_passant.intersects(rank)
+      && enemy_pawns.intersects(pawn)
+      && self.white.is_empty(self.en_passant | origin)
+      && self.black.is_empty(self.en_passant | origin);
+
+    if !valid {
+      errors.push(PositionError::EnPassant(self.en_passant));
+    }
+    //a double pawn push just happened, so the clock must have been reset
+    if self.halfmove_clock() != 0 {
+      errors.push(PositionError::EnPassantClock(self.halfmove_clock()));
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::board::{Board, bitboard::Bitboard, fen_error::FenError, position_error::PositionError};
+
+  fn errors(fen: &str) -> Vec<PositionError> {
+    Board::from_fen(fen).validate()
+  }
+
+  #[test]
+  fn valid() {
+    let fens = [
+      "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+      "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+      "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+      "8/8/1k6/2b5/2pP4/8/5K2/8 b - d3 0 1",
+    ];
+    for fen in fens {
+      assert_eq!(errors(fen), Vec::new());
+    }
+    assert_eq!(Board::default().validate(), Vec::new());
+  }
+
+  #[test]
+  fn kings() {
+    assert_eq!(
+      errors("4k3/8/8/8/8/8/8/8 w - - 0 1"),
+      vec![PositionError::KingCount {
+        white: true,
+        count: 0
+      }]
+    );
+    assert_eq!(
+      errors("4k3/8/8/8/8/8/8/K3K3 w - - 0 1"),
+      vec![PositionError::KingCount {
+        white: true,
+        count: 2
+      }]
+    );
+  }
+
+  #[test]
+  fn material() {
+    assert!(
+      errors("4k3/8/8/8/8/8/PPPPPPPP/P3K3 w - - 0 1").contains(&PositionError::PawnCount {
+        white: true,
+        count: 9
+      })
+    );
+    assert!(
+      errors("4k3/8/8/8/8/Q7/PPPPPPPP/QQQQK3 w - - 0 1").contains(
+        &PositionError::PromotedPieces {
+          white: true,
+          count: 4
+        }
+      )
+    );
+    assert!(
+      errors("4k3/8/8/NNNNNNNN/8/8/PPPPPPPP/RNBQK3 w - - 0 1").contains(
+        &PositionError::PieceCount {
+          white: true,
+          count: 21
+        }
+      )
+    );
+  }
+
+  #[test]
+  fn pawn_on_back_rank() {
+    assert_eq!(
+      errors("P3k3/8/8/8/8/8/8/4K2p w - - 0 1"),
+      vec![PositionError::PawnOnBackRank(Bitboard(0x01_00_00_00_00_00_00_80))]
+    );
+  }
+
+  #[test]
+  fn overlapping() {
+    let mut board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1");
+    board.white.queens |= board.black.king;
+    assert!(
+      board
+        .validate()
+        .contains(&PositionError::OverlappingPieces(board.black.king))
+    );
+  }
+
+  #[test]
+  fn opponent_in_check() {
+    assert_eq!(errors("4k3/8/8/8/8/8/8/R3K3 w - - 0 1"), Vec::new());
+    assert_eq!(errors("4k3/8/8/8/8/8/8/4K2R w - - 0 1"), Vec::new());
+    assert_eq!(
+      errors("4k3/8/8/8/8/8/8/4R1K1 w - - 0 1"),
+      vec![PositionError::OpponentInCheck]
+    );
+    assert_eq!(
+      errors("8/8/8/8/8/8/3k4/4K3 w - - 0 1"),
+      vec![PositionError::OpponentInCheck]
+    );
+  }
+
+  #[test]
+  fn castling_rights() {
+    assert_eq!(
+      errors("r3k3/8/8/8/8/8/8/R4K1R w KQq - 0 1"),
+      vec![
+        PositionError::CastlingRights {
+          white: true,
+          short: true
+        },
+        PositionError::CastlingRights {
+          white: true,
+          short: false
+        },
+      ]
+    );
+    assert_eq!(
+      errors("1r2k2r/8/8/8/8/8/8/4K3 w kq - 0 1"),
+      vec![PositionError::CastlingRights {
+        white: false,
+        short: false
+      }]
+    );
+  }
+
+  #[test]
+  fn en_passant() {
+    assert_eq!(errors("4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1"), Vec::new());
+    assert_eq!(
+      errors("4k3/8/8/8/3p4/8/8/4K3 b - e3 0 1"),
+      vec![PositionError::EnPassant(Bitboard(0x00_00_08_00_00_00_00_00))]
+    );
+    assert_eq!(
+      errors("4k3/8/8/8/3pP3/8/8/4K3 w - e3 0 1"),
+      vec![PositionError::EnPassant(Bitboard(0x00_00_08_00_00_00_00_00))]
+    );
+    assert_eq!(
+      errors("4k3/8/8/8/3pP3/8/8/4K3 b - e3 3 1"),
+      vec![PositionError::EnPassantClock(3)]
+    );
+    assert_eq!(
+      Board::try_from_fen_strict("4k3/8/8/8/3pP3/8/8/4K3 b - e3 3 1").err(),
+      Some(FenError::Illegal(vec![PositionError::EnPassantClock(3)]))
+    );
+  }
+
+  #[test]
+  fn every_error_reported() {
+    assert_eq!(errors("4k2P/8/8/8/8/8/8/8 w K - 0 1").len(), 3);
+  }
+
+  #[test]
+  fn strict_fen() {
+    assert!(Board::try_from_fen_strict("4k3/8/8/8/8/8/8/4K3 w - - 0 1").is_ok());
+    assert_eq!(
+      Board::try_from_fen_strict("4k3/8/8/8/8/8/8/4R1K1 w - - 0 1").err(),
+      Some(FenError::Illegal(vec![PositionError::OpponentInCheck]))
+    );
+    assert_eq!(
+      Board::try_from_fen_strict("4k3/8/8/8/8/8/8/4R1K1 x - - 0 1").err(),
+      Some(FenError::SideToMove(22))
+    );
+  }
+}
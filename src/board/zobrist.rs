@@ -0,0 +1,99 @@
+use super::{Board, board_movement_trait::BoardMovement, piece::PieceKind, pieces::Pieces};
+
+const fn split_mix(state: u64) -> (u64, u64) {
+  let state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+  let mut z = state;
+  z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+  z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+  (state, z ^ (z >> 31))
+}
+
+const fn generate<const N: usize>(seed: u64) -> [u64; N] {
+  let mut keys = [0; N];
+  let mut state = seed;
+  let mut i = 0;
+  while i < N {
+    let (next, key) = split_mix(state);
+    keys[i] = key;
+    state = next;
+    i += 1;
+  }
+  keys
+}
+
+//[color][piece kind][square], same kind order as PieceKind::ALL
+const PIECE_KEYS: [u64; 768] = generate(0x5A0B_2157);
+const CASTLING_KEYS: [u64; 4] = generate(0xCA57_11E5);
+const EN_PASSANT_KEYS: [u64; 8] = generate(0xE9A5_5A47);
+const WHITE_TURN_KEY: u64 = generate::<1>(0x7E11_0000)[0];
+
+fn pieces_key(pieces: &Pieces, color: usize) -> u64 {
+  let mut key = 0;
+  for kind in PieceKind::ALL {
+    for square in pieces.get(kind) {
+      key ^= PIECE_KEYS[(color * 6 + kind.index()) * 64 + square.index()];
+    }
+  }
+  key
+}
+
+fn castling_key(board: &Board) -> u64 {
+  let rights = [
+    board.white_short_castle,
+    board.white_long_castle,
+    board.black_short_castle,
+    board.black_long_castle,
+  ];
+
+  rights
+    .iter()
+    .zip(CASTLING_KEYS)
+    .filter(|(right, _)| **right)
+    .fold(0, |key, (_, right_key)| key ^ right_key)
+}
+
+//the en passant file only matters when a pawn is actually able to capture on it
+fn en_passant_key(board: &Board) -> u64 {
+  let (ally_pawns, from_rank) = if board.white_turn {
+    (board.white.pawns, board.en_passant.move_down_mask(1))
+  } else {
+    (board.black.pawns, board.en_passant.move_up_mask(1))
+  };
+  let attackers = from_rank.move_left_mask(1) | from_rank.move_right_mask(1);
+
+  match board.en_passant() {
+    Some(square) if attackers.intersects(ally_pawns) => EN_PASSANT_KEYS[square.index() % 8],
+    _ => 0,
+  }
+}
+
+fn turn_key(board: &Board) -> u64 {
+  if board.white_turn { WHITE_TURN_KEY } else { 0 }
+}
+
+pub(super) fn hash(board: &Board) -> u64 {
+  pieces_key(&board.white, 0)
+    ^ pieces_key(&board.black, 1)
+    ^ castling_key(board)
+    ^ en_passant_key(board)
+    ^ turn_key(board)
+}
+
+//everything that is not a piece, xoring it twice removes it from the hash
+pub(super) fn state_key(board: &Board) -> u64 {
+  castling_key(board) ^ en_passant_key(board) ^ turn_key(board)
+}
+
+//only the squares that changed between the two piece sets are touched
+pub(super) fn pieces_delta(before: &Pieces, after: &Pieces, color: usize) -> u64 {
+  let changed = Pieces {
+    pawns: before.pawns ^ after.pawns,
+    knights: before.knights ^ after.knights,
+    bishops: before.bishops ^ after.bishops,
+    rooks: before.rooks ^ after.rooks,
+    queens: before.queens ^ after.queens,
+    king: before.king ^ after.king,
+  };
+
+  pieces_key(&changed, color)
+}
@@ -1,10 +1,4 @@
-use crate::{
-  board::{
-    Board,
-    move_input::{BISHOP, KNIGHT, QUEEN, ROOK},
-  },
-  board_position_lookup::CENTER_LOOKUP,
-};
+use crate::board_position_lookup::CENTER_LOOKUP;
 use bevy::{
   asset::{AssetServer, Handle},
   color::Alpha,
@@ -18,12 +12,17 @@ use bevy::{
   sprite::Sprite,
   transform::components::Transform,
 };
+use chess_engine::board::{
+  Board,
+  bitboard::Bitboard,
+  piece::{Color, PieceKind},
+};
 
 #[derive(Component)]
 pub struct PieceTag;
 
 #[derive(Component)]
-pub struct PromotionTag(pub u64);
+pub struct PromotionTag(pub PieceKind);
 
 const BOARD_Z: f32 = 0.0;
 const PIECES_Z: f32 = 0.1;
@@ -90,11 +89,10 @@ impl BoardAssets {
   }
 
   pub fn draw_pieces(&mut self, commands: &mut Commands, board: &Board) {
-    for (i, lookup) in CENTER_LOOKUP.into_iter().enumerate() {
-      let at_mask = 1 << i;
-      if board.is_empty(at_mask) {
+    for (square, lookup) in Bitboard::FULL.into_iter().zip(CENTER_LOOKUP) {
+      let Some((color, kind)) = board.piece_at(square) else {
         continue;
-      }
+      };
 
       let transform = Transform {
         translation: lookup + Vec3::new(0.0, 0.0, PIECES_Z),
@@ -102,61 +100,24 @@ impl BoardAssets {
         rotation: Quat::IDENTITY,
       };
 
-      let (pieces, assets) = if board.white.is_empty(at_mask) {
-        (&board.black, &self.black)
-      } else {
-        (&board.white, &self.white)
+      let assets = match color {
+        Color::White => &self.white,
+        Color::Black => &self.black,
+      };
+      let image = match kind {
+        PieceKind::Pawn => &assets.pawn,
+        PieceKind::Knight => &assets.knight,
+        PieceKind::Bishop => &assets.bishop,
+        PieceKind::Rook => &assets.rook,
+        PieceKind::Queen => &assets.queen,
+        PieceKind::King => &assets.king,
       };
 
-      if pieces.is_pawn(at_mask) {
-        self.piece_ids.push(
-          commands
-            .spawn((Sprite::from_image(assets.pawn.clone()), transform, PieceTag))
-            .id(),
-        );
-      } else if pieces.is_knight(at_mask) {
-        self.piece_ids.push(
-          commands
-            .spawn((
-              Sprite::from_image(assets.knight.clone()),
-              transform,
-              PieceTag,
-            ))
-            .id(),
-        );
-      } else if pieces.is_bishop(at_mask) {
-        self.piece_ids.push(
-          commands
-            .spawn((
-              Sprite::from_image(assets.bishop.clone()),
-              transform,
-              PieceTag,
-            ))
-            .id(),
-        );
-      } else if pieces.is_rook(at_mask) {
-        self.piece_ids.push(
-          commands
-            .spawn((Sprite::from_image(assets.rook.clone()), transform, PieceTag))
-            .id(),
-        );
-      } else if pieces.is_queen(at_mask) {
-        self.piece_ids.push(
-          commands
-            .spawn((
-              Sprite::from_image(assets.queen.clone()),
-              transform,
-              PieceTag,
-            ))
-            .id(),
-        );
-      } else if pieces.is_king(at_mask) {
-        self.piece_ids.push(
-          commands
-            .spawn((Sprite::from_image(assets.king.clone()), transform, PieceTag))
-            .id(),
-        );
-      }
+      self.piece_ids.push(
+        commands
+          .spawn((Sprite::from_image(image.clone()), transform, PieceTag))
+          .id(),
+      );
     }
   }
 
@@ -169,15 +130,9 @@ impl BoardAssets {
   }
 
   pub fn draw_moves(&mut self, commands: &mut Commands, board: &Board) {
-    let moves = board.cached_moves.all();
-
-    for (i, lookup) in CENTER_LOOKUP.into_iter().enumerate() {
-      if moves & (1 << i) == 0 {
-        continue;
-      }
-
+    for to in board.cached_moves.all() {
       let transform = Transform {
-        translation: lookup + Vec3::new(0.0, 0.0, MOVES_Z),
+        translation: CENTER_LOOKUP[to.index()] + Vec3::new(0.0, 0.0, MOVES_Z),
         scale: Vec3::new(0.2, 0.2, 1.0),
         rotation: Quat::IDENTITY,
       };
@@ -210,7 +165,7 @@ impl BoardAssets {
         scale: Vec3::new(0.75, 0.75, 1.0),
         rotation: Quat::IDENTITY,
       },
-      PromotionTag(QUEEN),
+      PromotionTag(PieceKind::Queen),
     );
 
     let rook = (
@@ -220,7 +175,7 @@ impl BoardAssets {
         scale: Vec3::new(0.75, 0.75, 1.0),
         rotation: Quat::IDENTITY,
       },
-      PromotionTag(ROOK),
+      PromotionTag(PieceKind::Rook),
     );
 
     let bishop = (
@@ -230,7 +185,7 @@ impl BoardAssets {
         scale: Vec3::new(0.75, 0.75, 1.0),
         rotation: Quat::IDENTITY,
       },
-      PromotionTag(BISHOP),
+      PromotionTag(PieceKind::Bishop),
     );
 
     let knight = (
@@ -240,7 +195,7 @@ impl BoardAssets {
         scale: Vec3::new(0.75, 0.75, 1.0),
         rotation: Quat::IDENTITY,
       },
-      PromotionTag(KNIGHT),
+      PromotionTag(PieceKind::Knight),
     );
 
     self.promotion_ids.push(commands.spawn(queen).id());
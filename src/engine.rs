@@ -0,0 +1,51 @@
+use bevy::{
+  ecs::system::Resource,
+  tasks::{AsyncComputeTaskPool, Task, block_on, poll_once},
+};
+use chess_engine::{
+  board::Board,
+  search::{Limits, SearchResult, Searcher},
+};
+
+//enough for a few plies in the middlegame
+const ENGINE_NODES: u64 = 200_000;
+
+//one searcher for the whole session so its hash table carries over between moves,
+//it travels to the compute pool while searching and comes back with the result
+#[derive(Resource)]
+pub struct Engine {
+  searcher: Option<Searcher>,
+  task: Option<Task<(Searcher, u64, SearchResult)>>,
+}
+
+impl Default for Engine {
+  fn default() -> Engine {
+    Engine {
+      searcher: Some(Searcher::new()),
+      task: None,
+    }
+  }
+}
+
+impl Engine {
+  //searches a copy of the board in the background, unless a search is already running
+  pub fn start(&mut self, board: &Board) {
+    let Some(mut searcher) = self.searcher.take() else {
+      return;
+    };
+    let board = board.clone();
+    self.task = Some(AsyncComputeTaskPool::get().spawn(async move {
+      let result = searcher.search(&board, Limits::nodes(ENGINE_NODES));
+      (searcher, board.hash(), result)
+    }));
+  }
+
+  //the finished search with the hash of the position it was started from
+  pub fn poll(&mut self) -> Option<(u64, SearchResult)> {
+    let task = self.task.as_mut()?;
+    let (searcher, hash, result) = block_on(poll_once(task))?;
+    self.task = None;
+    self.searcher = Some(searcher);
+    Some((hash, result))
+  }
+}
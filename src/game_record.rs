@@ -0,0 +1,77 @@
+use bevy::ecs::system::Resource;
+use chess_engine::board::{Board, pgn::PgnGame};
+use std::{
+  fs::{self, OpenOptions},
+  io::{self, Write},
+  path::PathBuf,
+  time::{SystemTime, UNIX_EPOCH},
+};
+
+const GAMES_DIR: &str = "games";
+
+#[derive(Resource)]
+pub struct GameRecord(pub PgnGame);
+
+impl Default for GameRecord {
+  fn default() -> GameRecord {
+    let mut game = PgnGame::new(Board::default());
+    game.set_tag("Event", "Casual game");
+    game.set_tag("Site", "chess-engine");
+    game.set_tag("Date", &today());
+    game.set_tag("White", "White");
+    game.set_tag("Black", "Black");
+    GameRecord(game)
+  }
+}
+
+impl GameRecord {
+  //writes games/<unix seconds>.pgn and returns its path, never replacing an earlier save
+  pub fn save(&self) -> io::Result<PathBuf> {
+    fs::create_dir_all(GAMES_DIR)?;
+    let seconds = unix_seconds();
+
+    //saves within the same second get -1, -2, ... appended
+    for sequence in 0.. {
+      let name = match sequence {
+        0 => format!("{seconds}.pgn"),
+        _ => format!("{seconds}-{sequence}.pgn"),
+      };
+      let path = PathBuf::from(GAMES_DIR).join(name);
+      match OpenOptions::new().write(true).create_new(true).open(&path) {
+        Ok(mut file) => {
+          file.write_all(self.0.to_pgn().as_bytes())?;
+          return Ok(path);
+        }
+        Err(error) if error.kind() == io::ErrorKind::AlreadyExists => continue,
+        Err(error) => return Err(error),
+      }
+    }
+    unreachable!("there is always a free sequence number")
+  }
+}
+
+fn unix_seconds() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map_or(0, |duration| duration.as_secs())
+}
+
+//civil date from days since the epoch, see http://howardhinnant.github.io/date_algorithms.html
+fn today() -> String {
+  let days = (unix_seconds() / 86_400) as i64 + 719_468;
+  let era = days.div_euclid(146_097);
+  let day_of_era = days.rem_euclid(146_097);
+  let year_of_era =
+    (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+  let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+  let shifted_month = (5 * day_of_year + 2) / 153;
+
+  let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+  let month = if shifted_month < 10 {
+    shifted_month + 3
+  } else {
+    shifted_month - 9
+  };
+  let year = year_of_era + era * 400 + i64::from(month <= 2);
+  format!("{year:04}.{month:02}.{day:02}")
+}
@@ -1,11 +1,20 @@
-mod board;
 mod board_assets;
 mod board_position_lookup;
+mod engine;
+mod game_record;
 
 use bevy::{prelude::*, window::PrimaryWindow};
-use board::{move_input::MoveInput, status::PLAYING, Board};
+use chess_engine::board::{
+  Board,
+  move_input::MoveInput,
+  piece::PieceKind,
+  square::Square,
+  status::GameStatus,
+};
 use board_assets::{BoardAssets, PieceTag, PromotionTag};
 use board_position_lookup::{CENTER_LOOKUP, X_LOOKUP, Y_LOOKUP};
+use engine::Engine;
+use game_record::GameRecord;
 
 #[derive(Resource, Default)]
 enum DrawMode {
@@ -25,9 +34,11 @@ struct State {
   entity: Option<Entity>,
   selected_from: Option<usize>,
   selected_to: Option<usize>,
-  selected_promotion: Option<u64>,
+  selected_promotion: Option<PieceKind>,
 }
 
+type PromotionQuery<'a> = (&'a Transform, &'a PromotionTag);
+
 #[derive(Resource, Default)]
 struct MouseData {
   x: f32,
@@ -52,7 +63,10 @@ fn main() {
   App::new()
     .add_plugins(plugins)
     .add_systems(Startup, (setup, initial_draw).chain())
-    .add_systems(Update, (update_mouse_data, update_state, draw).chain())
+    .add_systems(
+      Update,
+      (update_mouse_data, update_state, handle_keys, engine_move, draw).chain(),
+    )
     .run();
 }
 
@@ -60,6 +74,8 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
   commands.spawn(Camera2d);
 
   commands.insert_resource(Board::default());
+  commands.insert_resource(GameRecord::default());
+  commands.insert_resource(Engine::default());
   commands.insert_resource(MouseData::default());
   commands.insert_resource(BoardAssets::new(asset_server));
   commands.insert_resource(State::default());
@@ -83,19 +99,19 @@ fn draw(
       assets.draw_moves(&mut commands, &board);
     }
     DrawMode::DragPiece => {
-      if let Some(entity) = state.entity {
-        if let Ok((_, mut transform, _)) = sprite_query.get_mut(entity) {
-          transform.translation.x = mouse.x;
-          transform.translation.y = mouse.y;
-          transform.translation.z = 1.0;
-        }
+      if let Some(entity) = state.entity
+        && let Ok((_, mut transform, _)) = sprite_query.get_mut(entity)
+      {
+        transform.translation.x = mouse.x;
+        transform.translation.y = mouse.y;
+        transform.translation.z = 1.0;
       }
     }
     DrawMode::DrawPromotion => {
-      if let Some(entity) = state.entity {
-        if let Ok((_, mut transform, _)) = sprite_query.get_mut(entity) {
-          transform.translation.z = -transform.translation.z;
-        }
+      if let Some(entity) = state.entity
+        && let Ok((_, mut transform, _)) = sprite_query.get_mut(entity)
+      {
+        transform.translation.z = -transform.translation.z;
       }
 
       if let Some(from_mask) = state.selected_from {
@@ -140,23 +156,114 @@ fn update_mouse_data(
   }
 }
 
+fn save_game(record: &GameRecord) {
+  match record.save() {
+    Ok(path) => info!("saved game to {}", path.display()),
+    Err(e) => error!("could not save game: {e}"),
+  }
+}
+
+fn play(board: &mut Board, record: &mut GameRecord, input: MoveInput) {
+  if board.move_piece(input) {
+    record.0.push(input);
+  }
+
+  let status = board.get_status();
+  if status.is_over() {
+    end_game(board, record, status);
+  }
+}
+
+fn end_game(board: &mut Board, record: &mut GameRecord, status: GameStatus) {
+  info!("{status}");
+  record.0.finish(status);
+  save_game(record);
+  *board = Board::default();
+  *record = GameRecord::default();
+}
+
+//s saves the game so far, r resigns for the side to move, d agrees to a draw,
+//c claims a threefold repetition or fifty-move draw, e lets the engine move
+fn handle_keys(
+  keys: Res<ButtonInput<KeyCode>>,
+  mut state: ResMut<State>,
+  mut board: ResMut<Board>,
+  mut record: ResMut<GameRecord>,
+  mut engine: ResMut<Engine>,
+) {
+  if keys.just_pressed(KeyCode::KeyS) {
+    save_game(&record);
+  }
+
+  if keys.just_pressed(KeyCode::KeyE) {
+    engine.start(&board);
+    return;
+  }
+
+  let status = if keys.just_pressed(KeyCode::KeyR) {
+    GameStatus::Resignation {
+      winner: !board.side_to_move(),
+    }
+  } else if keys.just_pressed(KeyCode::KeyD) {
+    GameStatus::AgreedDraw
+  } else if keys.just_pressed(KeyCode::KeyC)
+    && let Some(draw) = board.claimable_draw()
+  {
+    draw
+  } else {
+    return;
+  };
+  end_game(&mut board, &mut record, status);
+  state.mode = DrawMode::Reset;
+}
+
+fn engine_move(
+  mut state: ResMut<State>,
+  mut board: ResMut<Board>,
+  mut record: ResMut<GameRecord>,
+  mut engine: ResMut<Engine>,
+) {
+  let Some((hash, result)) = engine.poll() else {
+    return;
+  };
+  //the position changed while the engine was thinking, the move belongs to another one
+  if hash != board.hash() {
+    return;
+  }
+
+  if let Some(input) = result.best_move {
+    info!(
+      "engine plays {} (score {}, depth {}, hashfull {})",
+      input.to_uci(),
+      result.score,
+      result.depth,
+      result.hashfull
+    );
+    play(&mut board, &mut record, input);
+  }
+  state.mode = DrawMode::Reset;
+}
+
 fn update_state(
   mouse: Res<MouseData>,
   mut state: ResMut<State>,
   mut board: ResMut<Board>,
+  mut record: ResMut<GameRecord>,
   pieces: Query<(Entity, &mut Transform), With<PieceTag>>,
-  promotions: Query<(&Transform, &PromotionTag), (With<PromotionTag>, Without<PieceTag>)>,
+  promotions: Query<PromotionQuery, (With<PromotionTag>, Without<PieceTag>)>,
 ) {
   match state.mode {
     DrawMode::SelectPiece if mouse.just_pressed => {
-      let from_mask = 1 << mouse.board_pos;
+      let Some(from) = Square::from_index(mouse.board_pos) else {
+        return;
+      };
 
-      if board.is_empty(from_mask) || board.white_turn != board.is_white(from_mask) {
+      if board.is_empty(from) || board.white_turn != board.is_white(from) {
         return;
       }
 
       state.selected_from = Some(mouse.board_pos);
-      board.update_cache(from_mask);
+      board.update_cache(from);
 
       for (entity, transform) in pieces.iter() {
         if transform.translation.truncate() == CENTER_LOOKUP[mouse.board_pos].truncate() {
@@ -172,12 +279,11 @@ fn update_state(
     DrawMode::DragPiece if mouse.just_released => {
       state.selected_to = Some(mouse.board_pos);
 
-      let to_mask = 1 << mouse.board_pos;
-      if board.is_promotion(to_mask) {
+      let to = Square::from_index(mouse.board_pos);
+      if to.is_some_and(|to| board.is_promotion(to)) {
         state.mode = DrawMode::DrawPromotion;
       } else {
         state.mode = DrawMode::MakeMove;
-        state.selected_promotion = Some(0);
       }
     }
     DrawMode::DrawPromotion => {
@@ -197,20 +303,15 @@ fn update_state(
       }
     }
     DrawMode::MakeMove => {
-      if let Some(from) = state.selected_from {
-        if let Some(to) = state.selected_to {
-          if let Some(promotion) = state.selected_promotion {
-            board.move_piece(MoveInput {
-              from: 1 << from,
-              to: 1 << to,
-              promotion,
-            });
-
-            if board.get_status() != PLAYING {
-              *board = Board::default();
-            }
-          }
-        }
+      if let Some(from) = state.selected_from.and_then(Square::from_index)
+        && let Some(to) = state.selected_to.and_then(Square::from_index)
+      {
+        let input = MoveInput {
+          from,
+          to,
+          promotion: state.selected_promotion,
+        };
+        play(&mut board, &mut record, input);
       }
       state.mode = DrawMode::Reset;
     }
@@ -0,0 +1,143 @@
+use crate::board::{
+  Board,
+  piece::{Color, PieceKind},
+};
+
+//centipawns, indexed by PieceKind::index
+pub const PIECE_VALUES: [i32; 6] = [100, 320, 330, 500, 900, 0];
+
+//piece square tables from white's point of view, written as the board is seen: a8 first, h1 last
+#[rustfmt::skip]
+const PAWN_TABLE: [i32; 64] = [
+   0,  0,  0,  0,  0,  0,  0,  0,
+  50, 50, 50, 50, 50, 50, 50, 50,
+  10, 10, 20, 30, 30, 20, 10, 10,
+   5,  5, 10, 25, 25, 10,  5,  5,
+   0,  0,  0, 20, 20,  0,  0,  0,
+   5, -5,-10,  0,  0,-10, -5,  5,
+   5, 10, 10,-20,-20, 10, 10,  5,
+   0,  0,  0,  0,  0,  0,  0,  0,
+];
+
+#[rustfmt::skip]
+const KNIGHT_TABLE: [i32; 64] = [
+  -50,-40,-30,-30,-30,-30,-40,-50,
+  -40,-20,  0,  0,  0,  0,-20,-40,
+  -30,  0, 10, 15, 15, 10,  0,-30,
+  -30,  5, 15, 20, 20, 15,  5,-30,
+  -30,  0, 15, 20, 20, 15,  0,-30,
+  -30,  5, 10, 15, 15, 10,  5,-30,
+  -40,-20,  0,  5,  5,  0,-20,-40,
+  -50,-40,-30,-30,-30,-30,-40,-50,
+];
+
+#[rustfmt::skip]
+const BISHOP_TABLE: [i32; 64] = [
+  -20,-10,-10,-10,-10,-10,-10,-20,
+  -10,  0,  0,  0,  0,  0,  0,-10,
+  -10,  0,  5, 10, 10,  5,  0,-10,
+  -10,  5,  5, 10, 10,  5,  5,-10,
+  -10,  0, 10, 10, 10, 10,  0,-10,
+  -10, 10, 10, 10, 10, 10, 10,-10,
+  -10,  5,  0,  0,  0,  0,  5,-10,
+  -20,-10,-10,-10,-10,-10,-10,-20,
+];
+
+#[rustfmt::skip]
+const ROOK_TABLE: [i32; 64] = [
+   0,  0,  0,  0,  0,  0,  0,  0,
+   5, 10, 10, 10, 10, 10, 10,  5,
+  -5,  0,  0,  0,  0,  0,  0, -5,
+  -5,  0,  0,  0,  0,  0,  0, -5,
+  -5,  0,  0,  0,  0,  0,  0, -5,
+  -5,  0,  0,  0,  0,  0,  0, -5,
+  -5,  0,  0,  0,  0,  0,  0, -5,
+   0,  0,  0,  5,  5,  0,  0,  0,
+];
+
+#[rustfmt::skip]
+const QUEEN_TABLE: [i32; 64] = [
+  -20,-10,-10, -5, -5,-10,-10,-20,
+  -10,  0,  0,  0,  0,  0,  0,-10,
+  -10,  0,  5,  5,  5,  5,  0,-10,
+   -5,  0,  5,  5,  5,  5,  0, -5,
+    0,  0,  5,  5,  5,  5,  0, -5,
+  -10,  5,  5,  5,  5,  5,  0,-10,
+  -10,  0,  5,  0,  0,  0,  0,-10,
+  -20,-10,-10, -5, -5,-10,-10,-20,
+];
+
+#[rustfmt::skip]
+const KING_TABLE: [i32; 64] = [
+  -30,-40,-40,-50,-50,-40,-40,-30,
+  -30,-40,-40,-50,-50,-40,-40,-30,
+  -30,-40,-40,-50,-50,-40,-40,-30,
+  -30,-40,-40,-50,-50,-40,-40,-30,
+  -20,-30,-30,-40,-40,-30,-30,-20,
+  -10,-20,-20,-20,-20,-20,-20,-10,
+   20, 20,  0,  0,  0,  0, 20, 20,
+   20, 30, 10,  0,  0, 10, 30, 20,
+];
+
+const TABLES: [&[i32; 64]; 6] = [
+  &PAWN_TABLE,
+  &KNIGHT_TABLE,
+  &BISHOP_TABLE,
+  &ROOK_TABLE,
+  &QUEEN_TABLE,
+  &KING_TABLE,
+];
+
+//bit 0 is h8 while the tables start at a8, black reads them upside down
+fn table_index(square: usize, color: Color) -> usize {
+  match color {
+    Color::White => square ^ 7,
+    Color::Black => square ^ 7 ^ 56,
+  }
+}
+
+fn material_and_position(board: &Board, color: Color) -> i32 {
+  let pieces = board.pieces(color);
+  let mut score = 0;
+  for kind in PieceKind::ALL {
+    for square in pieces.get(kind) {
+      let table = TABLES[kind.index()];
+      score += PIECE_VALUES[kind.index()] + table[table_index(square.index(), color)];
+    }
+  }
+  score
+}
+
+//static score in centipawns from the side to move's point of view
+pub fn evaluate(board: &Board) -> i32 {
+  let color = board.side_to_move();
+  material_and_position(board, color) - material_and_position(board, !color)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{board::Board, search::eval::evaluate};
+
+  #[test]
+  fn symmetric() {
+    assert_eq!(evaluate(&Board::default()), 0);
+    assert_eq!(
+      evaluate(&Board::from_fen("4k3/pppp4/8/8/8/8/PPPP4/4K3 w - - 0 1")),
+      evaluate(&Board::from_fen("4k3/pppp4/8/8/8/8/PPPP4/4K3 b - - 0 1"))
+    );
+  }
+
+  #[test]
+  fn side_to_move() {
+    let white_up = "4k3/8/8/8/8/8/8/3QK3";
+    assert!(evaluate(&Board::from_fen(&format!("{white_up} w - - 0 1"))) > 800);
+    assert!(evaluate(&Board::from_fen(&format!("{white_up} b - - 0 1"))) < -800);
+  }
+
+  #[test]
+  fn centralisation() {
+    let rim = Board::from_fen("4k3/8/8/8/8/8/8/N3K3 w - - 0 1");
+    let center = Board::from_fen("4k3/8/8/8/3N4/8/8/4K3 w - - 0 1");
+    assert!(evaluate(&center) > evaluate(&rim));
+  }
+}
@@ -0,0 +1,8 @@
+pub mod eval;
+pub mod ordering;
+#[allow(clippy::module_inception)]
+mod search;
+pub mod see;
+pub mod tt;
+
+pub use search::{Limits, MATE, MAX_DEPTH, SearchResult, Searcher};
@@ -0,0 +1,183 @@
+use super::{MAX_DEPTH, eval::PIECE_VALUES, see::see};
+use crate::board::{
+  Board,
+  move_input::MoveInput,
+  move_list::{Move, MoveList},
+  piece::PieceKind,
+};
+
+const HASH_MOVE: i32 = 1_000_000;
+const TACTICAL: i32 = 500_000;
+const KILLER: i32 = 400_000;
+//quiet moves stay below the killers however good their history
+const HISTORY_LIMIT: i32 = 300_000;
+
+//hash move, then winning captures and promotions by mvv-lva, then killers, then quiets by history,
+//then captures that lose material
+pub struct MoveOrdering {
+  //two quiet moves per ply that caused a cutoff in a sibling node
+  killers: [[Option<MoveInput>; 2]; MAX_DEPTH as usize + 1],
+  //by side to move, from and to square
+  history: [[[i32; 64]; 64]; 2],
+}
+
+//constructor
+impl Default for MoveOrdering {
+  fn default() -> MoveOrdering {
+    MoveOrdering {
+      killers: [[None; 2]; MAX_DEPTH as usize + 1],
+      history: [[[0; 64]; 64]; 2],
+    }
+  }
+}
+
+//state
+impl MoveOrdering {
+  pub fn clear(&mut self) {
+    *self = MoveOrdering::default();
+  }
+
+  //killers belong to the old position, history is still a fair hint at half weight
+  pub fn new_search(&mut self) {
+    self.killers = [[None; 2]; MAX_DEPTH as usize + 1];
+    self.age_history();
+  }
+
+  fn age_history(&mut self) {
+    self.history.iter_mut().flatten().flatten().for_each(|score| *score /= 2);
+  }
+
+  pub fn killers(&self, ply: u32) -> [Option<MoveInput>; 2] {
+    self.killers[ply as usize]
+  }
+
+  pub fn history(&self, board: &Board, m: &Move) -> i32 {
+    self.history[board.side_to_move().index()][m.from.index()][m.to.index()]
+  }
+
+  pub fn score(&self, board: &Board, m: &Move, hash_move: Option<MoveInput>, ply: u32) -> i32 {
+    if Some(m.input()) == hash_move {
+      return HASH_MOVE;
+    }
+    if is_tactical(m) {
+      let exchange = see(board, m);
+      return if exchange >= 0 { TACTICAL + mvv_lva(board, m) } else { exchange };
+    }
+
+    let killers = self.killers(ply);
+    if Some(m.input()) == killers[0] {
+      KILLER
+    } else if Some(m.input()) == killers[1] {
+      KILLER - 1
+    } else {
+      self.history(board, m)
+    }
+  }
+
+  pub fn order(&self, board: &Board, moves: &mut MoveList, hash_move: Option<MoveInput>, ply: u32) {
+    moves.sort_by_cached_key(|m| -self.score(board, m, hash_move, ply));
+  }
+}
+
+//moving/updating
+impl MoveOrdering {
+  //a quiet move refuted the opponent's last move, try it early in siblings and elsewhere
+  pub fn cutoff(&mut self, board: &Board, m: &Move, depth: u32, ply: u32) {
+    if is_tactical(m) {
+      return;
+    }
+
+    let killers = &mut self.killers[ply as usize];
+    if killers[0] != Some(m.input()) {
+      killers[1] = killers[0];
+      killers[0] = Some(m.input());
+    }
+
+    let score = &mut self.history[board.side_to_move().index()][m.from.index()][m.to.index()];
+    *score += (depth * depth) as i32;
+    if *score >= HISTORY_LIMIT {
+      self.age_history();
+    }
+  }
+}
+
+fn is_tactical(m: &Move) -> bool {
+  m.is_capture() || m.promotion.is_some()
+}
+
+//most valuable victim first, the least valuable attacker breaking ties
+fn mvv_lva(board: &Board, m: &Move) -> i32 {
+  let attacker = board.piece_at(m.from).map_or(PieceKind::Pawn, |(_, kind)| kind);
+  material_gain(board, m) * 8 - attacker.index() as i32
+}
+
+//what the move wins at best, the captured piece plus whatever a pawn promotes to
+pub fn material_gain(board: &Board, m: &Move) -> i32 {
+  let captured = match board.piece_at(m.to) {
+    Some((_, kind)) => PIECE_VALUES[kind.index()],
+    None if m.is_en_passant() => PIECE_VALUES[PieceKind::Pawn.index()],
+    None => 0,
+  };
+  let promoted = m.promotion.map_or(0, |kind| {
+    PIECE_VALUES[kind.index()] - PIECE_VALUES[PieceKind::Pawn.index()]
+  });
+  captured + promoted
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    board::{Board, move_input::MoveInput},
+    search::ordering::MoveOrdering,
+  };
+
+  fn ordered(ordering: &MoveOrdering, board: &Board, hash_move: Option<MoveInput>) -> Vec<String> {
+    let mut moves = board.legal_moves();
+    ordering.order(board, &mut moves, hash_move, 0);
+    moves.iter().map(|m| m.input().to_uci()).collect()
+  }
+
+  #[test]
+  fn mvv_lva() {
+    //the queen before the rook, and the pawn is the cheapest way to take it
+    let board = Board::from_fen("4k3/8/8/2q5/1P1r4/1N6/8/4K3 w - - 0 1");
+    let moves = ordered(&MoveOrdering::default(), &board, None);
+    assert_eq!(moves[..3], ["b4c5", "b3c5", "b3d4"]);
+  }
+
+  #[test]
+  fn losing_captures_last() {
+    //the pawn is defended, taking it costs the queen
+    let board = Board::from_fen("4k3/8/2p5/3p4/8/8/8/3QK3 w - - 0 1");
+    let moves = ordered(&MoveOrdering::default(), &board, None);
+    assert_eq!(moves.last().unwrap(), "d1d5");
+  }
+
+  #[test]
+  fn hash_move_first() {
+    let board = Board::from_fen("4k3/8/8/2q5/1P1r4/1N6/8/4K3 w - - 0 1");
+    let moves = ordered(&MoveOrdering::default(), &board, MoveInput::from_uci("e1f2"));
+    assert_eq!(moves[..2], ["e1f2", "b4c5"]);
+  }
+
+  #[test]
+  fn killers_and_history() {
+    let board = Board::default();
+    let mut ordering = MoveOrdering::default();
+    let find = |uci: &str| *board.legal_moves().iter().find(|m| m.input().to_uci() == uci).unwrap();
+
+    ordering.cutoff(&board, &find("g1f3"), 4, 0);
+    ordering.cutoff(&board, &find("e2e4"), 2, 0);
+    assert_eq!(ordered(&ordering, &board, None)[..2], ["e2e4", "g1f3"]);
+
+    //on another ply only the history is left, where the deeper cutoff weighs more
+    let mut moves = board.legal_moves();
+    ordering.order(&board, &mut moves, None, 1);
+    assert_eq!(moves[0].input().to_uci(), "g1f3");
+    assert_eq!(moves[1].input().to_uci(), "e2e4");
+
+    ordering.new_search();
+    assert_eq!(ordering.killers(0), [None, None]);
+    assert_eq!(ordering.history(&board, &find("g1f3")), 8);
+  }
+}
@@ -0,0 +1,399 @@
+use super::{
+  eval::evaluate,
+  ordering::{MoveOrdering, material_gain},
+  see::see,
+  tt::{Bound, TranspositionTable, score_from_tt, score_to_tt},
+};
+use crate::board::{Board, move_input::MoveInput};
+
+pub const MATE: i32 = 30_000;
+pub const MAX_DEPTH: u32 = 64;
+const INFINITY: i32 = 32_000;
+//a capture that can't lift the static score this close to alpha is not worth searching
+const DELTA_MARGIN: i32 = 200;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Limits {
+  pub depth: u32,
+  pub nodes: u64,
+}
+
+//constructors
+impl Limits {
+  pub fn depth(depth: u32) -> Limits {
+    Limits {
+      depth,
+      nodes: u64::MAX,
+    }
+  }
+
+  pub fn nodes(nodes: u64) -> Limits {
+    Limits {
+      depth: MAX_DEPTH,
+      nodes,
+    }
+  }
+}
+
+impl Default for Limits {
+  fn default() -> Limits {
+    Limits::depth(MAX_DEPTH)
+  }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SearchResult {
+  pub best_move: Option<MoveInput>,
+  //centipawns from the side to move's point of view, or a mate score
+  pub score: i32,
+  pub depth: u32,
+  pub nodes: u64,
+  pub pv: Vec<MoveInput>,
+  //permille of the transposition table written during this search
+  pub hashfull: u32,
+}
+
+impl SearchResult {
+  //moves until mate, negative when the side to move is the one getting mated
+  pub fn mate_in(&self) -> Option<i32> {
+    let plies = MATE - self.score.abs();
+    if plies > MAX_DEPTH as i32 {
+      return None;
+    }
+    let moves = (plies + 1) / 2;
+    Some(if self.score > 0 { moves } else { -moves })
+  }
+}
+
+#[derive(Default)]
+pub struct Searcher {
+  limits: Limits,
+  nodes: u64,
+  stopped: bool,
+  //a finished iteration to fall back on, only then may the node budget cut a search short
+  can_stop: bool,
+  tt: TranspositionTable,
+  ordering: MoveOrdering,
+}
+
+//constructors
+impl Searcher {
+  pub fn new() -> Searcher {
+    Searcher::default()
+  }
+
+  pub fn with_hash(megabytes: usize) -> Searcher {
+    Searcher {
+      tt: TranspositionTable::new(megabytes),
+      ..Searcher::default()
+    }
+  }
+}
+
+impl Searcher {
+  //forget everything learned, for a new game
+  pub fn clear_hash(&mut self) {
+    self.tt.clear();
+    self.ordering.clear();
+  }
+
+  //iterative deepening, every finished depth replaces the previous result
+  pub fn search(&mut self, board: &Board, limits: Limits) -> SearchResult {
+    let mut board = board.clone();
+    self.limits = limits;
+    self.nodes = 0;
+    self.stopped = false;
+    self.can_stop = false;
+    self.tt.new_search();
+    self.ordering.new_search();
+
+    let mut result = SearchResult::default();
+    for depth in 1..=limits.depth.min(MAX_DEPTH) {
+      let mut pv = Vec::new();
+      let score = self.negamax(&mut board, depth, 0, -INFINITY, INFINITY, &mut pv);
+      if self.stopped {
+        break;
+      }
+
+      result = SearchResult {
+        best_move: pv.first().copied(),
+        score,
+        depth,
+        nodes: self.nodes,
+        pv,
+        hashfull: self.tt.hashfull(),
+      };
+      self.can_stop = true;
+
+      //iterative deepening finds the shortest mate first
+      if result.mate_in().is_some() {
+        break;
+      }
+    }
+
+    result.nodes = self.nodes;
+    result.hashfull = self.tt.hashfull();
+    result
+  }
+
+  fn negamax(
+    &mut self,
+    board: &mut Board,
+    depth: u32,
+    ply: u32,
+    mut alpha: i32,
+    beta: i32,
+    pv: &mut Vec<MoveInput>,
+  ) -> i32 {
+    pv.clear();
+    if self.can_stop && self.nodes >= self.limits.nodes {
+      self.stopped = true;
+      return 0;
+    }
+    self.nodes += 1;
+
+    if ply > 0 && is_draw(board) {
+      return 0;
+    }
+    if depth == 0 {
+      return self.quiescence(board, ply, alpha, beta);
+    }
+
+    let key = board.hash();
+    let entry = self.tt.probe(key);
+    if let Some(entry) = entry.filter(|entry| ply > 0 && entry.depth as u32 >= depth) {
+      let score = score_from_tt(entry.score, ply);
+      let cutoff = match entry.bound {
+        Bound::Exact => Some(score),
+        Bound::Lower => (score >= beta).then_some(beta),
+        Bound::Upper => (score <= alpha).then_some(alpha),
+      };
+      if let Some(score) = cutoff {
+        pv.extend(entry.best_move.filter(|_| entry.bound == Bound::Exact));
+        return score;
+      }
+    }
+
+    let mut moves = board.legal_moves();
+    if moves.is_empty() {
+      return if board.in_check() { -MATE + ply as i32 } else { 0 };
+    }
+    let hash_move = entry.and_then(|entry| entry.best_move);
+    self.ordering.order(board, &mut moves, hash_move, ply);
+
+    let original_alpha = alpha;
+    let mut child_pv = Vec::new();
+    for m in moves {
+      let undo = board.make_move(m.input()).expect("legal moves can be made");
+      let score = -self.negamax(board, depth - 1, ply + 1, -beta, -alpha, &mut child_pv);
+      board.unmake_move(undo);
+
+      if self.stopped {
+        return 0;
+      }
+      if score > alpha {
+        alpha = score;
+        pv.clear();
+        pv.push(m.input());
+        pv.extend_from_slice(&child_pv);
+      }
+      if alpha >= beta {
+        self.ordering.cutoff(board, &m, depth, ply);
+        break;
+      }
+    }
+
+    let bound = if alpha >= beta {
+      Bound::Lower
+    } else if alpha > original_alpha {
+      Bound::Exact
+    } else {
+      Bound::Upper
+    };
+    let score = score_to_tt(alpha, ply);
+    self.tt.store(key, depth, bound, score, pv.first().copied());
+
+    alpha
+  }
+
+  //only captures and promotions from here on, unless in check where every evasion counts
+  fn quiescence(&mut self, board: &mut Board, ply: u32, mut alpha: i32, beta: i32) -> i32 {
+    if self.can_stop && self.nodes >= self.limits.nodes {
+      self.stopped = true;
+      return 0;
+    }
+    self.nodes += 1;
+
+    if ply >= MAX_DEPTH {
+      return evaluate(board);
+    }
+
+    let in_check = board.in_check();
+    let stand_pat = evaluate(board);
+    let mut moves = if in_check {
+      let evasions = board.legal_moves();
+      if evasions.is_empty() {
+        return -MATE + ply as i32;
+      }
+      evasions
+    } else {
+      //the side to move may decline every capture
+      if stand_pat >= beta {
+        return beta;
+      }
+      alpha = alpha.max(stand_pat);
+      board.legal_captures()
+    };
+    self.ordering.order(board, &mut moves, None, ply);
+
+    for m in moves {
+      if !in_check && stand_pat + material_gain(board, &m) + DELTA_MARGIN < alpha {
+        continue;
+      }
+      //a capture that loses the exchange won't raise a score the side could stand pat on
+      if !in_check && see(board, &m) < 0 {
+        continue;
+      }
+
+      let undo = board.make_move(m.input()).expect("legal moves can be made");
+      let score = -self.quiescence(board, ply + 1, -beta, -alpha);
+      board.unmake_move(undo);
+
+      if self.stopped {
+        return 0;
+      }
+      if score >= beta {
+        return beta;
+      }
+      alpha = alpha.max(score);
+    }
+
+    alpha
+  }
+}
+
+//a repetition inside the tree is scored as a draw straight away, the opponent could claim it anyway
+fn is_draw(board: &Board) -> bool {
+  board.halfmove_clock() >= 100 || board.repetitions() > 1 || board.insufficient_material()
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    board::{Board, move_input::MoveInput},
+    search::{Limits, Searcher},
+  };
+
+  fn best(fen: &str, depth: u32) -> String {
+    let result = Searcher::new().search(&Board::from_fen(fen), Limits::depth(depth));
+    result.best_move.unwrap().to_uci()
+  }
+
+  #[test]
+  fn mate_in_one() {
+    let board = Board::from_fen("6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 1");
+    let result = Searcher::new().search(&board, Limits::depth(4));
+    assert_eq!(result.best_move, MoveInput::from_uci("a1a8"));
+    assert_eq!(result.mate_in(), Some(1));
+    assert_eq!(result.depth, 1);
+  }
+
+  #[test]
+  fn mate_in_two() {
+    //two rooks roll the king up the board
+    let board = Board::from_fen("7k/8/8/8/8/8/R7/1R4K1 w - - 0 1");
+    let result = Searcher::new().search(&board, Limits::depth(4));
+    assert_eq!(result.mate_in(), Some(2));
+    assert_eq!(result.pv.len(), 3);
+  }
+
+  #[test]
+  fn getting_mated() {
+    let board = Board::from_fen("6k1/5ppp/8/8/8/8/r4PPP/6K1 w - - 0 1");
+    let result = Searcher::new().search(&board, Limits::depth(3));
+    assert_eq!(result.mate_in(), None);
+
+    let board = Board::from_fen("7k/8/8/8/8/1r6/r7/7K w - - 0 1");
+    let result = Searcher::new().search(&board, Limits::depth(3));
+    assert_eq!(result.mate_in(), Some(-1));
+  }
+
+  #[test]
+  fn wins_material() {
+    assert_eq!(best("4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1", 2), "d1d5");
+    //the knight is defended, taking it loses the queen
+    assert_ne!(best("4k3/8/2p5/3n4/8/8/8/3QK3 w - - 0 1", 2), "d1d5");
+  }
+
+  #[test]
+  fn horizon() {
+    //at depth one only quiescence sees the pawn recapture
+    assert_ne!(best("4k3/8/2p5/3p4/8/8/8/3QK3 w - - 0 1", 1), "d1d5");
+    //and that the rook can be taken back after winning the queen
+    assert_eq!(best("3rk3/8/8/3q4/8/8/3R4/3RK3 w - - 0 1", 1), "d2d5");
+  }
+
+  #[test]
+  fn quiet_position_score() {
+    let board = Board::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1");
+    let result = Searcher::new().search(&board, Limits::depth(1));
+    assert!(result.score > 50 && result.score < 200);
+
+    //in check at the horizon every evasion is searched, here taking the queen
+    let board = Board::from_fen("4k3/8/8/8/8/8/3q4/3RK3 w - - 0 1");
+    let result = Searcher::new().search(&board, Limits::depth(1));
+    assert!(result.score > 400);
+  }
+
+  #[test]
+  fn pv_is_playable() {
+    let board =
+      Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1");
+    let result = Searcher::new().search(&board, Limits::depth(3));
+    assert_eq!(result.depth, 3);
+    assert_eq!(result.pv.len(), 3);
+    assert_eq!(result.best_move, result.pv.first().copied());
+
+    let mut board = board.clone();
+    for input in result.pv {
+      assert!(board.move_piece(input));
+    }
+  }
+
+  #[test]
+  fn node_budget() {
+    let board = Board::default();
+    let result = Searcher::new().search(&board, Limits::nodes(2_000));
+    assert!(result.best_move.is_some());
+    assert!(result.depth >= 1);
+    //the budget is checked before every node
+    assert!(result.nodes <= 2_000);
+  }
+
+  #[test]
+  fn transposition_table() {
+    let board =
+      Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1");
+    let mut searcher = Searcher::with_hash(1);
+    let first = searcher.search(&board, Limits::depth(3));
+    assert!(first.hashfull > 0);
+
+    //the second search finds its answers already stored
+    let second = searcher.search(&board, Limits::depth(3));
+    assert!(second.nodes < first.nodes);
+    assert_eq!(second.best_move, first.best_move);
+    assert_eq!(second.score, first.score);
+
+    searcher.clear_hash();
+    let third = searcher.search(&board, Limits::depth(3));
+    assert_eq!(third.nodes, first.nodes);
+  }
+
+  #[test]
+  fn no_moves() {
+    let board = Board::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1");
+    let result = Searcher::new().search(&board, Limits::depth(3));
+    assert_eq!(result.best_move, None);
+    assert_eq!(result.score, 0);
+  }
+}
@@ -0,0 +1,129 @@
+use super::eval::PIECE_VALUES;
+use crate::board::{
+  Board,
+  bitboard::Bitboard,
+  move_list::Move,
+  piece::{Color, PieceKind},
+  square::Square,
+};
+
+fn value(kind: PieceKind) -> i32 {
+  PIECE_VALUES[kind.index()]
+}
+
+//material won or lost once both sides have traded on the target square for as long as it pays off
+pub fn see(board: &Board, m: &Move) -> i32 {
+  let to = m.to;
+  let mut occupancy = board.occupancy() ^ Bitboard::from(m.from);
+  let mover = board.piece_at(m.from).map_or(PieceKind::Pawn, |(_, kind)| kind);
+
+  let mut gains = [0; 32];
+  gains[0] = match board.piece_at(to) {
+    Some((_, kind)) => value(kind),
+    None if m.is_en_passant() => {
+      //the captured pawn sits beside the moving one, not on the target square
+      let captured = Square::new(to.file(), m.from.rank()).expect("file and rank of real squares");
+      occupancy ^= Bitboard::from(captured);
+      value(PieceKind::Pawn)
+    }
+    None => 0,
+  };
+  //the piece left standing on the square, which the next capture wins
+  let mut on_square = value(mover);
+  if let Some(promotion) = m.promotion {
+    gains[0] += value(promotion) - value(PieceKind::Pawn);
+    on_square = value(promotion);
+  }
+
+  let mut side = !board.side_to_move();
+  let mut depth = 0;
+  loop {
+    let attackers = board.attackers_to(to, occupancy);
+    let own = attackers & board.pieces(side).pieces_concat();
+    let Some((kind, from)) = least_valuable(board, side, own) else {
+      break;
+    };
+    //the king may only take when nothing is left to take it back
+    if kind == PieceKind::King && !(attackers & board.pieces(!side).pieces_concat()).is_empty() {
+      break;
+    }
+
+    depth += 1;
+    gains[depth] = on_square - gains[depth - 1];
+    on_square = value(kind);
+    occupancy ^= Bitboard::from(from);
+    side = !side;
+  }
+
+  //each side may stop capturing whenever carrying on would cost it
+  while depth > 0 {
+    gains[depth - 1] = -(-gains[depth - 1]).max(gains[depth]);
+    depth -= 1;
+  }
+  gains[0]
+}
+
+fn least_valuable(board: &Board, side: Color, attackers: Bitboard) -> Option<(PieceKind, Square)> {
+  PieceKind::ALL.iter().find_map(|&kind| {
+    let square = (board.pieces(side).get(kind) & attackers).first()?;
+    Some((kind, square))
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{board::Board, search::see::see};
+
+  fn see_of(fen: &str, uci: &str) -> i32 {
+    let board = Board::from_fen(fen);
+    let m = *board
+      .legal_moves()
+      .iter()
+      .find(|m| m.input().to_uci() == uci)
+      .unwrap();
+    see(&board, &m)
+  }
+
+  #[test]
+  fn undefended() {
+    assert_eq!(see_of("4k3/8/8/3r4/8/8/8/3RK3 w - - 0 1", "d1d5"), 500);
+    assert_eq!(see_of("4k3/8/8/8/8/8/8/3RK3 w - - 0 1", "d1d5"), 0);
+  }
+
+  #[test]
+  fn defended() {
+    //queen takes a pawn defended by a pawn
+    assert_eq!(see_of("4k3/8/2p5/3p4/8/8/8/3QK3 w - - 0 1", "d1d5"), 100 - 900);
+    //pawn takes a defended knight, the pawn is lost back
+    assert_eq!(see_of("4k3/8/2p5/3n4/4P3/8/8/4K3 w - - 0 1", "e4d5"), 320 - 100);
+    //a defended square with nothing on it
+    assert_eq!(see_of("4k3/8/2p5/8/8/8/8/3QK3 w - - 0 1", "d1d5"), -900);
+  }
+
+  #[test]
+  fn x_rays() {
+    //the doubled rooks win the exchange on d5, the one behind backs up the one in front
+    assert_eq!(see_of("3rk3/8/8/3p4/8/8/3R4/3RK3 w - - 0 1", "d2d5"), 100);
+    //without the second rook the pawn is defended once too often
+    assert_eq!(see_of("3rk3/8/8/3p4/8/8/3R4/4K3 w - - 0 1", "d2d5"), 100 - 500);
+    //a queen behind a bishop
+    assert_eq!(see_of("4k3/6b1/8/4p3/8/2B5/1Q6/4K3 w - - 0 1", "c3e5"), 100 - 330 + 330);
+  }
+
+  #[test]
+  fn king_recaptures() {
+    //the king takes back only when the square is not covered any more
+    assert_eq!(see_of("8/8/3k4/3p4/8/8/8/3RK3 w - - 0 1", "d1d5"), 100 - 500);
+    assert_eq!(see_of("8/8/3k4/3p4/8/8/3R4/3RK3 w - - 0 1", "d2d5"), 100);
+  }
+
+  #[test]
+  fn special_moves() {
+    assert_eq!(see_of("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "e5d6"), 100);
+    assert_eq!(see_of("4k3/2p5/8/3pP3/8/8/8/4K3 w - d6 0 1", "e5d6"), 0);
+    //the new queen is lost to the king, still up the rook and the promotion
+    assert_eq!(see_of("3rk3/4P3/8/8/8/8/8/4K3 w - - 0 1", "e7d8q"), 500 + 800 - 900);
+    //promoting on a square the rook covers gives the queen away
+    assert_eq!(see_of("r6k/4P3/8/8/8/8/8/4K3 w - - 0 1", "e7e8q"), -100);
+  }
+}
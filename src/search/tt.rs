@@ -0,0 +1,213 @@
+use super::search::{MATE, MAX_DEPTH};
+use crate::board::move_input::MoveInput;
+use std::mem::size_of;
+
+pub const DEFAULT_HASH_MB: usize = 16;
+
+//how the stored score relates to the true score of the position
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bound {
+  Exact,
+  //the search failed high, the true score is at least this
+  Lower,
+  //no move reached alpha, the true score is at most this
+  Upper,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Entry {
+  pub key: u64,
+  pub depth: u8,
+  pub bound: Bound,
+  pub score: i32,
+  pub best_move: Option<MoveInput>,
+  pub age: u8,
+}
+
+pub struct TranspositionTable {
+  entries: Vec<Option<Entry>>,
+  age: u8,
+}
+
+//constructors
+impl Default for TranspositionTable {
+  fn default() -> TranspositionTable {
+    TranspositionTable::new(DEFAULT_HASH_MB)
+  }
+}
+
+impl TranspositionTable {
+  pub fn new(megabytes: usize) -> TranspositionTable {
+    let len = (megabytes * 1024 * 1024 / size_of::<Option<Entry>>()).max(1);
+    TranspositionTable {
+      entries: vec![None; len],
+      age: 0,
+    }
+  }
+}
+
+//state
+impl TranspositionTable {
+  pub fn len(&self) -> usize {
+    self.entries.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.entries.iter().all(Option::is_none)
+  }
+
+  pub fn clear(&mut self) {
+    self.entries.fill(None);
+    self.age = 0;
+  }
+
+  //entries from earlier searches become the first to be replaced
+  pub fn new_search(&mut self) {
+    self.age = self.age.wrapping_add(1);
+  }
+
+  //permille of the table filled during the current search, sampled like uci engines do
+  pub fn hashfull(&self) -> u32 {
+    let sample = &self.entries[..self.entries.len().min(1000)];
+    let used = sample
+      .iter()
+      .filter(|entry| entry.is_some_and(|entry| entry.age == self.age))
+      .count();
+    (used * 1000 / sample.len()) as u32
+  }
+
+  fn index(&self, key: u64) -> usize {
+    ((key as u128 * self.entries.len() as u128) >> 64) as usize
+  }
+
+  pub fn probe(&self, key: u64) -> Option<Entry> {
+    self.entries[self.index(key)].filter(|entry| entry.key == key)
+  }
+
+  pub fn store(
+    &mut self,
+    key: u64,
+    depth: u32,
+    bound: Bound,
+    score: i32,
+    best_move: Option<MoveInput>,
+  ) {
+    let index = self.index(key);
+    let slot = &mut self.entries[index];
+
+    if let Some(old) = slot {
+      let replace = old.key == key || old.age != self.age || depth >= old.depth as u32;
+      if !replace {
+        return;
+      }
+    }
+
+    //an upper bound has no best move of its own, keep the one found earlier
+    let best_move = best_move.or(slot.filter(|old| old.key == key).and_then(|old| old.best_move));
+    *slot = Some(Entry {
+      key,
+      depth: depth as u8,
+      bound,
+      score,
+      best_move,
+      age: self.age,
+    });
+  }
+}
+
+//mate scores count plies from the root, the table keeps them relative to the stored position
+pub fn score_to_tt(score: i32, ply: u32) -> i32 {
+  if score >= MATE - MAX_DEPTH as i32 {
+    score + ply as i32
+  } else if score <= -MATE + MAX_DEPTH as i32 {
+    score - ply as i32
+  } else {
+    score
+  }
+}
+
+pub fn score_from_tt(score: i32, ply: u32) -> i32 {
+  if score >= MATE - MAX_DEPTH as i32 {
+    score - ply as i32
+  } else if score <= -MATE + MAX_DEPTH as i32 {
+    score + ply as i32
+  } else {
+    score
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    board::move_input::MoveInput,
+    search::{
+      MATE,
+      tt::{Bound, TranspositionTable, score_from_tt, score_to_tt},
+    },
+  };
+
+  #[test]
+  fn store_and_probe() {
+    let mut tt = TranspositionTable::new(1);
+    assert!(tt.is_empty());
+    assert_eq!(tt.probe(42), None);
+
+    let e2e4 = MoveInput::from_uci("e2e4");
+    tt.store(42, 3, Bound::Exact, 25, e2e4);
+    let entry = tt.probe(42).unwrap();
+    assert_eq!((entry.depth, entry.bound, entry.score), (3, Bound::Exact, 25));
+    assert_eq!(entry.best_move, e2e4);
+
+    //upper bounds keep the move found before
+    tt.store(42, 4, Bound::Upper, -10, None);
+    assert_eq!(tt.probe(42).unwrap().best_move, e2e4);
+    assert_eq!(tt.probe(42).unwrap().bound, Bound::Upper);
+  }
+
+  #[test]
+  fn replacement() {
+    let mut tt = TranspositionTable::new(0);
+    assert_eq!(tt.len(), 1);
+
+    tt.store(1, 5, Bound::Exact, 0, None);
+    //a shallower result for another position does not push out a deeper one
+    tt.store(2, 3, Bound::Exact, 0, None);
+    assert!(tt.probe(1).is_some());
+    assert!(tt.probe(2).is_none());
+
+    //unless it was stored during an earlier search
+    tt.new_search();
+    tt.store(2, 3, Bound::Exact, 0, None);
+    assert!(tt.probe(1).is_none());
+    assert!(tt.probe(2).is_some());
+  }
+
+  #[test]
+  fn hashfull() {
+    let mut tt = TranspositionTable::new(1);
+    assert_eq!(tt.hashfull(), 0);
+
+    //the smallest key landing in each of the first 500 slots
+    let len = tt.len() as u128;
+    for slot in 0..500u128 {
+      let key = (slot << 64).div_ceil(len);
+      tt.store(key as u64, 1, Bound::Exact, 0, None);
+    }
+    assert_eq!(tt.hashfull(), 500);
+
+    tt.new_search();
+    assert_eq!(tt.hashfull(), 0);
+    tt.clear();
+    assert!(tt.is_empty());
+  }
+
+  #[test]
+  fn mate_scores() {
+    //mate in 3 plies seen from 2 plies deep is mate in 1 ply from the stored position
+    assert_eq!(score_to_tt(MATE - 3, 2), MATE - 1);
+    assert_eq!(score_from_tt(MATE - 1, 2), MATE - 3);
+    assert_eq!(score_to_tt(-MATE + 4, 1), -MATE + 3);
+    assert_eq!(score_from_tt(score_to_tt(-MATE + 4, 3), 5), -MATE + 6);
+    assert_eq!(score_to_tt(150, 7), 150);
+  }
+}